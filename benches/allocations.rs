@@ -0,0 +1,108 @@
+//! Counts the allocations a shared matcher makes to hand its data to the widget of a variant.
+//!
+//! Run with `cargo bench --bench allocations`. Druid can't run widget passes outside of a window,
+//! so this calls the projection the matcher does at the start of each pass directly, once with
+//! data that doesn't change and once with a path that changes every frame.
+
+use druid::{widget::SizedBox, Data};
+use druid_enums::Matcher;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+#[derive(Clone, Data)]
+struct Shared {
+    user: String,
+    history: Arc<Vec<String>>,
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Screen {
+    Editor {
+        path: String,
+        lines: Arc<Vec<String>>,
+    },
+    Settings(String, u32),
+}
+
+// The passes of a frame: event, lifecycle, update, layout and paint.
+const PROJECTIONS_PER_FRAME: usize = 5;
+const FRAMES: usize = 1000;
+
+fn count(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    f();
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn main() {
+    let data = (
+        Shared {
+            user: String::from("someone"),
+            history: Arc::new(vec![String::from("opened"); 100]),
+        },
+        Screen::Editor {
+            path: String::from("src/lib.rs"),
+            lines: Arc::new(vec![String::from("line"); 1000]),
+        },
+    );
+
+    let mut matcher = Screen::shared_matcher()
+        .editor(SizedBox::empty())
+        .settings(SizedBox::empty());
+    matcher.project_(&data);
+    let unchanged = count(|| {
+        for _ in 0..FRAMES * PROJECTIONS_PER_FRAME {
+            matcher.project_(&data);
+        }
+    });
+
+    let paths: Vec<_> = (0..FRAMES)
+        .map(|frame| {
+            let mut data = data.clone();
+            if let Screen::Editor { path, .. } = &mut data.1 {
+                *path = format!("src/{}.rs", frame);
+            }
+            data
+        })
+        .collect();
+    let changed = count(|| {
+        for data in &paths {
+            for _ in 0..PROJECTIONS_PER_FRAME {
+                matcher.project_(data);
+            }
+        }
+    });
+
+    println!(
+        "unchanged data:          {:.2} allocations per frame",
+        unchanged as f64 / FRAMES as f64
+    );
+    println!(
+        "path changing per frame: {:.2} allocations per frame",
+        changed as f64 / FRAMES as f64
+    );
+}
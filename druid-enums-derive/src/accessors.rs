@@ -0,0 +1,140 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Fields, Ident};
+
+use crate::matcher::{data_of, fields_of, type_of};
+use crate::parse::{MatcherDerive, MatcherVariant};
+use crate::view::mentions;
+
+/// Generates `is_*`, `as_*`, `as_*_mut` and `into_*` for each variant, named after its builder,
+/// and `From` the data of each variant whose type no other variant has.
+pub fn accessors(input: &MatcherDerive) -> TokenStream {
+    let enum_name = &input.enum_name;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let methods = input.variants.iter().map(|variant| {
+        let variant_name = &variant.name;
+        let base_name = variant.resolve_base_name();
+        let is_name = format_ident!("is_{}", base_name);
+        let is_doc = format!("Whether this is a [`{}::{}`].", enum_name, variant_name);
+        let is = quote! {
+            #[doc = #is_doc]
+            pub fn #is_name(&self) -> bool {
+                matches!(self, #enum_name::#variant_name { .. })
+            }
+        };
+        if let Fields::Unit = variant.fields {
+            return is;
+        }
+        let (pattern, _, _) = data_of(enum_name, variant, "", &quote!());
+        let names: Vec<Ident> = fields_of(variant, "", &quote!())
+            .into_iter()
+            .map(|(_, name)| name)
+            .collect();
+        let types: Vec<_> = variant.fields.iter().map(|field| &field.ty).collect();
+        let (ref_ty, mut_ty, value) = if types.len() == 1 {
+            let ty = types[0];
+            let name = &names[0];
+            (quote!(&#ty), quote!(&mut #ty), quote!(#name))
+        } else {
+            (
+                quote!((#(&#types),*)),
+                quote!((#(&mut #types),*)),
+                quote!((#(#names),*)),
+            )
+        };
+        let variant_ty = type_of(variant, &input.generics);
+        let into_value = match &variant.fields {
+            Fields::Named(fields) => {
+                let view_name = variant.resolve_view_name();
+                let fields = fields.named.iter().flat_map(|field| &field.ident);
+                quote!(#view_name { #(#fields: #names),* })
+            }
+            _ => value.clone(),
+        };
+        let as_name = format_ident!("as_{}", base_name);
+        let as_mut_name = format_ident!("as_{}_mut", base_name);
+        let into_name = format_ident!("into_{}", base_name);
+        let as_doc = format!(
+            "The data of this [`{}::{}`], if it is one.",
+            enum_name, variant_name
+        );
+        quote! {
+            #is
+            #[doc = #as_doc]
+            pub fn #as_name(&self) -> Option<#ref_ty> {
+                match self {
+                    #enum_name::#variant_name #pattern => Some(#value),
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                }
+            }
+            #[doc = #as_doc]
+            pub fn #as_mut_name(&mut self) -> Option<#mut_ty> {
+                match self {
+                    #enum_name::#variant_name #pattern => Some(#value),
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                }
+            }
+            #[doc = #as_doc]
+            pub fn #into_name(self) -> Option<#variant_ty> {
+                match self {
+                    #enum_name::#variant_name #pattern => Some(#into_value),
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                }
+            }
+        }
+    });
+
+    // `From` impls for types more than one variant has would conflict.
+    let variant_tys: Vec<TokenStream> = input
+        .variants
+        .iter()
+        .map(|variant| type_of(variant, &input.generics))
+        .collect();
+    let candidates: Vec<(&MatcherVariant, &TokenStream)> = input
+        .variants
+        .iter()
+        .zip(&variant_tys)
+        .filter(|(variant, ty)| {
+            !matches!(variant.fields, Fields::Unit)
+                && variant_tys
+                    .iter()
+                    .filter(|other| other.to_string() == ty.to_string())
+                    .count()
+                    == 1
+        })
+        .collect();
+    // A type mentioning a generic parameter may be any of the others, so it only gets `From` if
+    // it's the only one.
+    let params: Vec<&Ident> = input
+        .generics
+        .type_params()
+        .map(|param| &param.ident)
+        .chain(input.generics.const_params().map(|param| &param.ident))
+        .collect();
+    let is_generic = |ty: &TokenStream| params.iter().any(|param| mentions(ty, param));
+    let from_impls = candidates
+        .iter()
+        .filter(|(_, ty)| candidates.len() == 1 || !is_generic(ty))
+        .map(|(variant, variant_ty)| {
+            let (_, _, rebuild) = data_of(enum_name, variant, "", &quote!(data));
+            quote! {
+                impl #impl_generics ::std::convert::From<#variant_ty> for #enum_name #ty_generics #where_clause {
+                    fn from(data: #variant_ty) -> Self {
+                        #rebuild
+                    }
+                }
+            }
+        });
+
+    quote! {
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            #(#methods)*
+        }
+
+        #(#from_impls)*
+    }
+}
@@ -0,0 +1,318 @@
+use heck::SnakeCase;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, GenericParam, Generics, Ident};
+
+use crate::matcher::{matcher_generics, type_of, widget_generics};
+use crate::parse::MatcherDerive;
+
+/// The module holding the traits an exhaustive matcher's state parameters need to implement.
+fn states_module(input: &MatcherDerive) -> Ident {
+    format_ident!(
+        "{}_derived_states",
+        input.enum_name.to_string().to_snake_case()
+    )
+}
+
+/// The name of the matcher wrapped by the exhaustive `matcher_name`, which implements `Widget`
+/// regardless of missing variants.
+pub fn unchecked_name(matcher_name: &Ident) -> Ident {
+    format_ident!("{}Unchecked", matcher_name)
+}
+
+/// Generates a trait for each variant, implemented by the state parameter of an exhaustive matcher
+/// once the variant has a widget or the matcher has a default.
+///
+/// They're only there to name the missing builder method when a matcher isn't complete.
+pub fn states(input: &MatcherDerive) -> TokenStream {
+    let visibility = &input.visibility;
+    let enum_name = &input.enum_name;
+    let module = states_module(input);
+    let traits = input.variants.iter().map(|variant| {
+        let variant_name = &variant.name;
+        let builder_name = variant.resolve_builder_name();
+        let lazy_builder_name = variant.resolve_lazy_builder_name();
+        let message = format!(
+            "`{}::{}` has no widget in this matcher",
+            enum_name, variant_name
+        );
+        let label = format!("this matcher is missing `.{}(...)`", builder_name);
+        let note = format!(
+            "set it with `.{}(...)` or `.{}(...)`, or give the matcher a `.default(...)`",
+            builder_name, lazy_builder_name
+        );
+        quote! {
+            #[diagnostic::on_unimplemented(message = #message, label = #label, note = #note)]
+            pub trait #variant_name<Default_> {}
+            impl<Default_> #variant_name<Default_> for ::druid_enums::matcher::Set {}
+            impl #variant_name<::druid_enums::matcher::Set> for ::druid_enums::matcher::Unset {}
+        }
+    });
+    let module_doc = format!(
+        "Whether the exhaustive matchers of [`{}`] have a widget for each variant.",
+        enum_name
+    );
+    quote! {
+        #[doc = #module_doc]
+        #[allow(non_camel_case_types)]
+        #visibility mod #module {
+            #(#traits)*
+        }
+    }
+}
+
+/// Generates the exhaustive `matcher_name`, a typestate builder around the matcher generated as
+/// `unchecked_name(matcher_name)` that only implements `Widget` once every variant has a widget.
+pub fn matcher(input: &MatcherDerive, matcher_name: &Ident, shared: Option<&Ident>) -> TokenStream {
+    let visibility = &input.visibility;
+    let enum_name = &input.enum_name;
+    let (_, enum_ty_generics, _) = input.generics.split_for_impl();
+    let enum_ty = quote!(#enum_name #enum_ty_generics);
+    let unchecked_name = unchecked_name(matcher_name);
+    let module = states_module(input);
+
+    let with_shared = |inner: TokenStream| match shared {
+        Some(shared) => quote!((#shared, #inner)),
+        None => inner,
+    };
+    let data_ty = with_shared(enum_ty.clone());
+    let hook_data_ty = with_shared(quote!(V));
+
+    let unchecked_generics = match shared {
+        Some(shared) => matcher_generics(&input.generics, shared),
+        None => input.generics.clone(),
+    };
+    let (unchecked_impl_generics, unchecked_ty_generics, unchecked_where_clause) =
+        unchecked_generics.split_for_impl();
+    let unchecked_args = generic_args(&unchecked_generics);
+
+    let variant_states: Vec<Ident> = input
+        .variants
+        .iter()
+        .map(|variant| input.resolve_state_ident(variant))
+        .collect();
+    let default_state = input.resolve_default_state_ident();
+    let states: Vec<&Ident> = variant_states
+        .iter()
+        .chain(std::iter::once(&default_state))
+        .collect();
+
+    let mut generics = unchecked_generics.clone();
+    for state in &states {
+        generics
+            .params
+            .push(parse_quote!(#state = ::druid_enums::matcher::Unset));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // The matcher type after `state` has been set.
+    let set = |state: &Ident| {
+        let states = states.iter().map(|other| {
+            if *other == state {
+                quote!(::druid_enums::matcher::Set)
+            } else {
+                quote!(#other)
+            }
+        });
+        quote!(#matcher_name<#(#unchecked_args,)* #(#states),*>)
+    };
+
+    let builder_fns = input.variants.iter().zip(&variant_states).map(|(variant, state)| {
+        let builder_name = variant.resolve_builder_name();
+        let lazy_builder_name = variant.resolve_lazy_builder_name();
+        let variant_ty = with_shared(type_of(variant, &input.generics));
+        let set_ty = set(state);
+        quote! {
+            pub fn #builder_name(self, widget: impl ::druid::Widget<#variant_ty> + 'static) -> #set_ty {
+                #matcher_name {
+                    unchecked: self.unchecked.#builder_name(widget),
+                    state: ::std::marker::PhantomData,
+                }
+            }
+            pub fn #lazy_builder_name<W: ::druid::Widget<#variant_ty> + 'static>(
+                self,
+                build: impl FnMut() -> W + 'static,
+            ) -> #set_ty {
+                #matcher_name {
+                    unchecked: self.unchecked.#lazy_builder_name(build),
+                    state: ::std::marker::PhantomData,
+                }
+            }
+        }
+    });
+    let default_set_ty = set(&default_state);
+
+    let mut complete_generics = widget_generics(input, &generics);
+    {
+        let predicates = &mut complete_generics.make_where_clause().predicates;
+        for (variant, state) in input.variants.iter().zip(&variant_states) {
+            let variant_name = &variant.name;
+            predicates.push(parse_quote!(#state: #module::#variant_name<#default_state>));
+        }
+    }
+    let (_, _, complete_where_clause) = complete_generics.split_for_impl();
+
+    let keep_alive_doc = format!("See [`{}::keep_alive`].", unchecked_name);
+    let on_missing_doc = format!("See [`{}::on_missing`].", unchecked_name);
+    let on_enter_doc = format!("See [`{}::on_enter`].", unchecked_name);
+    let on_exit_doc = format!("See [`{}::on_exit`].", unchecked_name);
+    let on_change_doc = format!("See [`{}::on_change`].", unchecked_name);
+    let transition_doc = format!("See [`{}::transition`].", unchecked_name);
+    let doc = format!(
+        "A matcher for [`{}`] that only implements `Widget` once every variant has a widget, or \
+         there is a default.",
+        enum_name
+    );
+    quote! {
+        #[doc = #doc]
+        #visibility struct #matcher_name #generics #where_clause {
+            unchecked: #unchecked_name #unchecked_ty_generics,
+            state: ::std::marker::PhantomData<fn() -> (#(#states,)*)>,
+        }
+
+        impl #unchecked_impl_generics #matcher_name #unchecked_ty_generics #unchecked_where_clause {
+            pub fn new() -> Self {
+                #matcher_name {
+                    unchecked: #unchecked_name::new(),
+                    state: ::std::marker::PhantomData,
+                }
+            }
+        }
+
+        impl #impl_generics #matcher_name #ty_generics #where_clause {
+            pub fn default(self, widget: impl ::druid::Widget<#data_ty> + 'static) -> #default_set_ty {
+                #matcher_name {
+                    unchecked: self.unchecked.default(widget),
+                    state: ::std::marker::PhantomData,
+                }
+            }
+            pub fn default_empty(self) -> #default_set_ty where #data_ty: ::druid::Data {
+                #matcher_name {
+                    unchecked: self.unchecked.default_empty(),
+                    state: ::std::marker::PhantomData,
+                }
+            }
+            #[doc = #keep_alive_doc]
+            pub fn keep_alive(self) -> Self {
+                #matcher_name {
+                    unchecked: self.unchecked.keep_alive(),
+                    state: ::std::marker::PhantomData,
+                }
+            }
+            #[doc = #on_missing_doc]
+            pub fn on_missing(self, missing: ::druid_enums::Missing) -> Self {
+                #matcher_name {
+                    unchecked: self.unchecked.on_missing(missing),
+                    state: ::std::marker::PhantomData,
+                }
+            }
+            #[doc = #on_enter_doc]
+            pub fn on_enter<V: ::druid::Data>(
+                self,
+                prism: impl ::druid_enums::Prism<#enum_ty, V> + 'static,
+                hook: impl FnMut(&mut ::druid::UpdateCtx, &#hook_data_ty, &::druid::Env) + 'static,
+            ) -> Self {
+                #matcher_name {
+                    unchecked: self.unchecked.on_enter(prism, hook),
+                    state: ::std::marker::PhantomData,
+                }
+            }
+            #[doc = #on_exit_doc]
+            pub fn on_exit<V: ::druid::Data>(
+                self,
+                prism: impl ::druid_enums::Prism<#enum_ty, V> + 'static,
+                hook: impl FnMut(&mut ::druid::UpdateCtx, &#hook_data_ty, &::druid::Env) + 'static,
+            ) -> Self {
+                #matcher_name {
+                    unchecked: self.unchecked.on_exit(prism, hook),
+                    state: ::std::marker::PhantomData,
+                }
+            }
+            #[doc = #on_change_doc]
+            pub fn on_change(
+                self,
+                hook: impl FnMut(&mut ::druid::UpdateCtx, &#data_ty, &#data_ty, &::druid::Env) + 'static,
+            ) -> Self {
+                #matcher_name {
+                    unchecked: self.unchecked.on_change(hook),
+                    state: ::std::marker::PhantomData,
+                }
+            }
+            #[doc = #transition_doc]
+            pub fn transition(self, transition: ::druid_enums::Transition) -> Self {
+                #matcher_name {
+                    unchecked: self.unchecked.transition(transition),
+                    state: ::std::marker::PhantomData,
+                }
+            }
+            #(#builder_fns)*
+            /// The matcher without the check for missing variants.
+            pub fn unchecked(self) -> #unchecked_name #unchecked_ty_generics {
+                self.unchecked
+            }
+        }
+
+        impl #impl_generics ::druid::Widget<#data_ty> for #matcher_name #ty_generics #complete_where_clause {
+            fn event(
+                &mut self,
+                ctx: &mut ::druid::EventCtx,
+                event: &::druid::Event,
+                data: &mut #data_ty,
+                env: &::druid::Env
+            ) {
+                self.unchecked.event(ctx, event, data, env)
+            }
+            fn lifecycle(
+                &mut self,
+                ctx: &mut ::druid::LifeCycleCtx,
+                event: &::druid::LifeCycle,
+                data: &#data_ty,
+                env: &::druid::Env
+            ) {
+                self.unchecked.lifecycle(ctx, event, data, env)
+            }
+            fn update(&mut self,
+                ctx: &mut ::druid::UpdateCtx,
+                old_data: &#data_ty,
+                data: &#data_ty,
+                env: &::druid::Env
+            ) {
+                self.unchecked.update(ctx, old_data, data, env)
+            }
+            fn layout(
+                &mut self,
+                ctx: &mut ::druid::LayoutCtx,
+                bc: &::druid::BoxConstraints,
+                data: &#data_ty,
+                env: &::druid::Env
+            ) -> ::druid::Size {
+                self.unchecked.layout(ctx, bc, data, env)
+            }
+            fn paint(&mut self, ctx: &mut ::druid::PaintCtx, data: &#data_ty, env: &::druid::Env) {
+                self.unchecked.paint(ctx, data, env)
+            }
+        }
+    }
+}
+
+/// The arguments naming each of `generics`' parameters, e.g. `'a, T, N` for `<'a, T: Data, const N: usize>`.
+fn generic_args(generics: &Generics) -> Vec<TokenStream> {
+    generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Lifetime(param) => {
+                let lifetime = &param.lifetime;
+                quote!(#lifetime)
+            }
+            GenericParam::Type(param) => {
+                let ident = &param.ident;
+                quote!(#ident)
+            }
+            GenericParam::Const(param) => {
+                let ident = &param.ident;
+                quote!(#ident)
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,96 @@
+use quote::quote;
+use syn::{parse_macro_input, Fields};
+
+mod accessors;
+mod exhaustive;
+mod matchable;
+mod matcher;
+mod parse;
+mod prism;
+mod selector;
+mod view;
+use parse::MatcherDerive;
+
+#[proc_macro_derive(Matcher, attributes(matcher))]
+pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as MatcherDerive);
+
+    let visibility = &input.visibility;
+    let enum_name = &input.enum_name;
+    let matcher_name = input.resolve_matcher_name();
+    let shared_matcher_name = input.resolve_shared_matcher_name();
+    let shared = input.resolve_shared_ident();
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let shared_generics = matcher::matcher_generics(&input.generics, &shared);
+    let (_, shared_ty_generics, _) = shared_generics.split_for_impl();
+
+    let view_structs = input
+        .variants
+        .iter()
+        .filter_map(|variant| match &variant.fields {
+            Fields::Named(fields) => Some(view::view_struct(
+                visibility,
+                enum_name,
+                &input.generics,
+                variant,
+                fields,
+            )),
+            _ => None,
+        });
+
+    let (plain_matcher, shared_matcher, states) = if input.exhaustive {
+        let unchecked_name = exhaustive::unchecked_name(&matcher_name);
+        let unchecked_shared_name = exhaustive::unchecked_name(&shared_matcher_name);
+        let plain_matcher = matcher::matcher(&input, &unchecked_name, None);
+        let shared_matcher = matcher::matcher(&input, &unchecked_shared_name, Some(&shared));
+        let exhaustive_matcher = exhaustive::matcher(&input, &matcher_name, None);
+        let exhaustive_shared_matcher =
+            exhaustive::matcher(&input, &shared_matcher_name, Some(&shared));
+        (
+            quote!(#plain_matcher #exhaustive_matcher),
+            quote!(#shared_matcher #exhaustive_shared_matcher),
+            exhaustive::states(&input),
+        )
+    } else {
+        (
+            matcher::matcher(&input, &matcher_name, None),
+            matcher::matcher(&input, &shared_matcher_name, Some(&shared)),
+            quote!(),
+        )
+    };
+    let matchable = matchable::matchable(&input);
+    let prisms = prism::prisms(&input);
+    let selectors = if input.selectors {
+        selector::selectors(&input)
+    } else {
+        quote!()
+    };
+    let accessors = if input.accessors {
+        accessors::accessors(&input)
+    } else {
+        quote!()
+    };
+
+    let output = quote! {
+        #(#view_structs)*
+
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            pub fn matcher() -> #matcher_name #ty_generics {
+                #matcher_name::new()
+            }
+            pub fn shared_matcher<#shared: ::druid::Data>() -> #shared_matcher_name #shared_ty_generics {
+                #shared_matcher_name::new()
+            }
+        }
+
+        #plain_matcher
+        #shared_matcher
+        #states
+        #matchable
+        #prisms
+        #selectors
+        #accessors
+    };
+    output.into()
+}
@@ -0,0 +1,148 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{ext::IdentExt, Fields, Ident};
+
+use crate::matcher::{data_of, type_of};
+use crate::parse::{DefaultData, MatcherDerive, MatcherVariant};
+use crate::view::mentions;
+
+/// Generates the `Matchable` impl describing the variants of the enum.
+pub fn matchable(input: &MatcherDerive) -> TokenStream {
+    let enum_name = &input.enum_name;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let variant_names: Vec<String> = input
+        .variants
+        .iter()
+        .map(|variant| variant.name.unraw().to_string())
+        .collect();
+    let variant_labels = input.variants.iter().map(|variant| variant.resolve_label());
+    let variant_count = input.variants.len();
+    let index_match = input.variants.iter().enumerate().map(|(index, variant)| {
+        let variant_name = &variant.name;
+        quote!(#enum_name::#variant_name { .. } => #index)
+    });
+    // `default_for` can't require the bounds of generic fields, so it has no default for them.
+    let default_match =
+        input.variants.iter().enumerate().map(|(index, variant)| {
+            match default_value(input, variant) {
+                Some(value) if default_bounds(input, variant).is_empty() => {
+                    quote!(#index => Some(#value))
+                }
+                _ => quote!(#index => None),
+            }
+        });
+    let constructors = input.variants.iter().filter_map(|variant| {
+        let value = default_value(input, variant)?;
+        let constructor_name = format_ident!("default_{}", variant.resolve_base_name());
+        let bounds = default_bounds(input, variant);
+        let where_clause = if bounds.is_empty() {
+            quote!()
+        } else {
+            quote!(where #(#bounds),*)
+        };
+        let doc = format!("[`{}::{}`] with its default data.", enum_name, variant.name);
+        Some(quote! {
+            #[doc = #doc]
+            pub fn #constructor_name() -> Self #where_clause {
+                #value
+            }
+        })
+    });
+    // Enums without fields get shortcuts for the widgets choosing a variant.
+    let choice_fns = if input
+        .variants
+        .iter()
+        .all(|variant| matches!(variant.fields, Fields::Unit))
+    {
+        quote! {
+            /// A radio button for each variant, see [`druid_enums::choice::radio_group`].
+            pub fn radio_group() -> impl ::druid::Widget<Self> {
+                ::druid_enums::choice::radio_group()
+            }
+            /// A dropdown listing the variants, see [`druid_enums::choice::dropdown`].
+            pub fn dropdown() -> impl ::druid::Widget<Self> {
+                ::druid_enums::choice::dropdown()
+            }
+        }
+    } else {
+        quote!()
+    };
+    quote! {
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            #(#constructors)*
+            #choice_fns
+        }
+
+        impl #impl_generics ::druid_enums::Matchable for #enum_name #ty_generics #where_clause {
+            const VARIANT_NAMES: &'static [&'static str] = &[#(#variant_names),*];
+            const VARIANT_LABELS: &'static [&'static str] = &[#(#variant_labels),*];
+            const VARIANT_COUNT: usize = #variant_count;
+
+            fn variant_index(&self) -> usize {
+                match *self {
+                    #(#index_match,)*
+                }
+            }
+
+            fn default_for(index: usize) -> Option<Self> {
+                match index {
+                    #(#default_match,)*
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+/// The `Default` bounds `#[matcher(default)]` needs on the fields of `variant` that mention the
+/// generic parameters of the enum. The others are checked where the default is built.
+fn default_bounds(input: &MatcherDerive, variant: &MatcherVariant) -> Vec<TokenStream> {
+    if let Some(DefaultData::Default) = variant.default_data {
+        let params: Vec<&Ident> = input
+            .generics
+            .type_params()
+            .map(|param| &param.ident)
+            .chain(input.generics.const_params().map(|param| &param.ident))
+            .collect();
+        variant
+            .fields
+            .iter()
+            .map(|field| &field.ty)
+            .filter(|ty| {
+                let ty = ty.to_token_stream();
+                params.iter().any(|param| mentions(&ty, param))
+            })
+            .map(|ty| quote!(#ty: ::std::default::Default))
+            .collect()
+    } else {
+        Vec::new()
+    }
+}
+
+/// Builds `variant` with its default data, if it has any: unit variants always do, others need a
+/// `#[matcher(default)]` or `#[matcher(default_data = ...)]`.
+fn default_value(input: &MatcherDerive, variant: &MatcherVariant) -> Option<TokenStream> {
+    let enum_name = &input.enum_name;
+    let variant_name = &variant.name;
+    let default = quote!(::std::default::Default::default());
+    match (&variant.fields, &variant.default_data) {
+        (Fields::Unit, _) => Some(quote!(#enum_name::#variant_name)),
+        (_, None) => None,
+        (Fields::Unnamed(fields), Some(DefaultData::Default)) => {
+            let defaults = fields.unnamed.iter().map(|_| &default);
+            Some(quote!(#enum_name::#variant_name(#(#defaults),*)))
+        }
+        (Fields::Named(fields), Some(DefaultData::Default)) => {
+            let fields = fields.named.iter().flat_map(|field| &field.ident);
+            Some(quote!(#enum_name::#variant_name { #(#fields: #default),* }))
+        }
+        (_, Some(DefaultData::Expr(expr))) => {
+            let variant_ty = type_of(variant, &input.generics);
+            let (_, _, rebuild) = data_of(enum_name, variant, "", &quote!(d));
+            Some(quote! {{
+                let d: #variant_ty = #expr;
+                #rebuild
+            }})
+        }
+    }
+}
@@ -0,0 +1,1014 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{ext::IdentExt, parse_quote, Fields, GenericParam, Generics, Ident};
+
+use crate::parse::{MatcherDerive, MatcherVariant};
+use crate::selector::selector_name;
+use crate::view::{self, SameFn};
+
+/// Generates a matcher struct, its builder methods and its `Widget` impl.
+///
+/// With a `shared` parameter the matcher is a `Widget<(Shared, Enum)>` and the widget of each
+/// variant gets the shared data alongside its own, otherwise it's a plain `Widget<Enum>`.
+pub fn matcher(input: &MatcherDerive, matcher_name: &Ident, shared: Option<&Ident>) -> TokenStream {
+    let visibility = &input.visibility;
+    let enum_name = &input.enum_name;
+    let (_, enum_ty_generics, _) = input.generics.split_for_impl();
+    let enum_ty = quote!(#enum_name #enum_ty_generics);
+
+    let matcher_generics = match shared {
+        Some(shared) => matcher_generics(&input.generics, shared),
+        None => input.generics.clone(),
+    };
+    let (impl_generics, ty_generics, where_clause) = matcher_generics.split_for_impl();
+
+    // The data of a widget that gets `inner` along with the shared data, if any.
+    let with_shared = |inner: TokenStream| match shared {
+        Some(shared) => quote!((#shared, #inner)),
+        None => inner,
+    };
+    // Builds the data for a variant widget from the owned `value` of the variant.
+    let shared_with = |value: TokenStream| match shared {
+        Some(_) => quote!((data.0.to_owned(), #value)),
+        None => value,
+    };
+    // Where the variant's part of the widget data ends up, and where the enum is in `data`.
+    let (variant_data, enum_data) = match shared {
+        Some(_) => (quote!(d.1), quote!(data.1)),
+        None => (quote!(d), quote!(*data)),
+    };
+    // Where the variant's part is in `d`, the `&mut` to the data a variant widget sees.
+    let cached_variant_data = match shared {
+        Some(_) => quote!(d.1),
+        None => quote!((*d)),
+    };
+    // Keeps the shared part of the data a variant widget sees up to date, and writes it back.
+    let (refresh_shared, write_back_shared) = match shared {
+        Some(_) => (
+            quote!(::druid_enums::matcher::refresh(&mut d.0, &data.0);),
+            quote!(::druid_enums::matcher::write_back(&d.0, &mut data.0);),
+        ),
+        None => (quote!(), quote!()),
+    };
+    let old_enum_data = match shared {
+        Some(_) => quote!(old_data.1),
+        None => quote!(*old_data),
+    };
+    let outgoing_enum = match shared {
+        Some(_) => quote!(outgoing.1),
+        None => quote!(outgoing),
+    };
+    let data_ty = with_shared(enum_ty.clone());
+    // What `on_enter` and `on_exit` hooks see, given the variant's data in `value`.
+    let hook_data_ty = with_shared(quote!(V));
+    let hook_data = match shared {
+        Some(_) => shared_with(quote!(value.to_owned())),
+        None => quote!(*value),
+    };
+
+    let struct_fields = input.variants.iter().map(|variant| {
+        let builder_name = variant.resolve_builder_name();
+        let factory_name = factory_name(variant);
+        let data_name = data_name(variant);
+        let variant_ty = with_shared(type_of(variant, &input.generics));
+        quote! {
+            #builder_name: Option<::druid::WidgetPod<#variant_ty, Box<dyn ::druid::Widget<#variant_ty>>>>,
+            #factory_name: Option<Box<dyn FnMut() -> Box<dyn ::druid::Widget<#variant_ty>>>>,
+            #data_name: Option<#variant_ty>
+        }
+    });
+
+    let struct_defaults = input.variants.iter().map(|variant| {
+        let builder_name = variant.resolve_builder_name();
+        let factory_name = factory_name(variant);
+        let data_name = data_name(variant);
+        quote!(#builder_name: None, #factory_name: None, #data_name: None)
+    });
+
+    let builder_fns = input.variants.iter().map(|variant| {
+        let builder_name = variant.resolve_builder_name();
+        let lazy_builder_name = variant.resolve_lazy_builder_name();
+        let factory_name = factory_name(variant);
+        let variant_ty = with_shared(type_of(variant, &input.generics));
+        quote! {
+            pub fn #builder_name(mut self, widget: impl ::druid::Widget<#variant_ty> + 'static) -> Self {
+                self.#builder_name = Some(::druid::WidgetPod::new(Box::new(widget)));
+                self.#factory_name = None;
+                self
+            }
+            pub fn #lazy_builder_name<W: ::druid::Widget<#variant_ty> + 'static>(
+                mut self,
+                mut build: impl FnMut() -> W + 'static,
+            ) -> Self {
+                self.#builder_name = None;
+                self.#factory_name = Some(Box::new(move || Box::new(build())));
+                self
+            }
+        }
+    });
+
+    // Not `Matchable::variant_name`, which may need more bounds than the matcher has.
+    let variant_name_match = input
+        .variants
+        .iter()
+        .map(|variant| {
+            let variant_name = &variant.name;
+            let name = variant_name.unraw().to_string();
+            quote!(#enum_name::#variant_name { .. } => #name)
+        })
+        .collect::<Vec<_>>();
+    let widget_added_checks = input.variants.iter().map(|variant| {
+        let builder_name = variant.resolve_builder_name();
+        let builder = builder_name.unraw().to_string();
+        let factory_name = factory_name(variant);
+        quote! {
+            if self.default_.is_none()
+                && self.#builder_name.is_none()
+                && self.#factory_name.is_none()
+                && ::druid_enums::matcher::missing(
+                    self.missing_,
+                    stringify!(#matcher_name),
+                    #builder,
+                    ctx.widget_id(),
+                )
+            {
+                let placeholder = ::druid_enums::matcher::placeholder(
+                    stringify!(#enum_name),
+                    |data: &#data_ty| match &#enum_data {
+                        #(#variant_name_match,)*
+                    },
+                );
+                self.default_ = Some(::druid::WidgetPod::new(Box::new(placeholder)));
+            }
+        }
+    });
+
+    let project_match = input.variants.iter().map(|variant| {
+        let variant_name = &variant.name;
+        let data_name = data_name(variant);
+        let (data_pattern, data_values, _) = data_of(enum_name, variant, "", &variant_data);
+        let widget_data = shared_with(data_values);
+        let refresh: Vec<TokenStream> = fields_of(variant, "", &cached_variant_data)
+            .into_iter()
+            .zip(same_fns(variant))
+            .map(|((place, binding), same_fn)| refresh_field(&same_fn, &place, &binding))
+            .chain(std::iter::once(refresh_shared.clone()))
+            .filter(|refresh| !refresh.is_empty())
+            .collect();
+        let cached = if refresh.is_empty() {
+            quote!(Some(_) => (),)
+        } else {
+            quote!(Some(d) => { #(#refresh)* })
+        };
+        quote! {
+            #enum_name::#variant_name #data_pattern => match &mut self.#data_name {
+                #cached
+                None => self.#data_name = Some(#widget_data),
+            }
+        }
+    });
+
+    let write_back_match = input.variants.iter().map(|variant| {
+        let data_name = data_name(variant);
+        let variant_name = &variant.name;
+        let (data_pattern, _, _) = data_of(enum_name, variant, "", &variant_data);
+        let write_back: Vec<TokenStream> = fields_of(variant, "", &cached_variant_data)
+            .into_iter()
+            .zip(same_fns(variant))
+            .map(|((place, binding), same_fn)| write_back_field(&same_fn, &place, &binding))
+            .chain(std::iter::once(write_back_shared.clone()))
+            .filter(|write_back| !write_back.is_empty())
+            .collect();
+        if write_back.is_empty() {
+            quote!(#enum_name::#variant_name { .. } => (),)
+        } else {
+            quote! {
+                #enum_name::#variant_name #data_pattern => if let Some(d) = &self.#data_name {
+                    #(#write_back)*
+                },
+            }
+        }
+    });
+
+    let event_match = input.variants.iter().map(|variant| {
+        let builder_name = variant.resolve_builder_name();
+        let data_name = data_name(variant);
+        let variant_name = &variant.name;
+        quote! {
+            #enum_name::#variant_name { .. } => match (&mut self.#builder_name, &mut self.#data_name) {
+                (Some(widget), Some(d)) => {
+                    widget.event(ctx, event, d, env);
+                    self.write_back_(data);
+                },
+                _ => if let Some(default) = &mut self.default_ {
+                    default.event(ctx, event, data, env);
+                },
+            }
+        }
+    });
+
+    let same_match = input.variants.iter().map(|variant| {
+        let variant_name = &variant.name;
+        let (old_pattern, _, _) = data_of(enum_name, variant, "old_", &variant_data);
+        let (data_pattern, _, _) = data_of(enum_name, variant, "", &variant_data);
+        let old_fields = fields_of(variant, "old_", &quote!());
+        let same = fields_of(variant, "", &quote!())
+            .into_iter()
+            .zip(old_fields)
+            .zip(same_fns(variant))
+            .filter_map(
+                |(((_, binding), (_, old_binding)), same_fn)| match same_fn {
+                    SameFn::Data => Some(quote!(::druid::Data::same(#old_binding, #binding))),
+                    SameFn::Path(path) => Some(quote!(#path(#old_binding, #binding))),
+                    SameFn::Ignore => None,
+                },
+            );
+        quote! {
+            (#enum_name::#variant_name #old_pattern, #enum_name::#variant_name #data_pattern) => {
+                true #(&& #same)*
+            }
+        }
+    });
+    let same_shared = match shared {
+        Some(_) => quote!(::druid::Data::same(&old_data.0, &data.0) &&),
+        None => quote!(),
+    };
+
+    let lifecycle_match = input.variants.iter().map(|variant| {
+        let builder_name = variant.resolve_builder_name();
+        let data_name = data_name(variant);
+        let variant_name = &variant.name;
+        quote! {
+            #enum_name::#variant_name { .. } => match (&mut self.#builder_name, &self.#data_name) {
+                (Some(widget), Some(d)) => widget.lifecycle(ctx, event, d, env),
+                _ => if let Some(default) = &mut self.default_ {
+                    default.lifecycle(ctx, event, data, env);
+                },
+            }
+        }
+    });
+
+    let update_match = input.variants.iter().map(|variant| {
+        let builder_name = variant.resolve_builder_name();
+        let data_name = data_name(variant);
+        let variant_name = &variant.name;
+        quote! {
+            #enum_name::#variant_name { .. } => match (&mut self.#builder_name, &self.#data_name) {
+                (Some(widget), Some(d)) => widget.update(ctx, d, env),
+                _ => if let Some(default) = &mut self.default_ {
+                    default.update(ctx, data, env);
+                },
+            }
+        }
+    });
+
+    let layout_match = input.variants.iter().map(|variant| {
+        let builder_name = variant.resolve_builder_name();
+        let data_name = data_name(variant);
+        let variant_name = &variant.name;
+        quote! {
+            #enum_name::#variant_name { .. } => match (&mut self.#builder_name, &self.#data_name) {
+                (Some(widget), Some(d)) => {
+                    let size = widget.layout(ctx, bc, d, env);
+                    widget.set_layout_rect(ctx, d, env, size.to_rect());
+                    size
+                },
+                _ => match &mut self.default_ {
+                    Some(default) => {
+                        let size = default.layout(ctx, bc, data, env);
+                        default.set_layout_rect(ctx, data, env, size.to_rect());
+                        size
+                    }
+                    None => bc.min(),
+                },
+            }
+        }
+    });
+
+    let paint_match = input.variants.iter().map(|variant| {
+        let builder_name = variant.resolve_builder_name();
+        let data_name = data_name(variant);
+        let variant_name = &variant.name;
+        quote! {
+            #enum_name::#variant_name { .. } => match (&mut self.#builder_name, &self.#data_name) {
+                (Some(widget), Some(d)) => widget.paint(ctx, d, env),
+                _ => if let Some(default) = &mut self.default_ {
+                    default.paint(ctx, data, env);
+                },
+            }
+        }
+    });
+
+    let selector_checks = input.variants.iter().map(|variant| {
+        let selector_name = selector_name(variant);
+        let (_, _, rebuild) = data_of(enum_name, variant, "", &quote!(value));
+        let switch = quote! {
+            #enum_data = #rebuild;
+            ctx.set_handled();
+            return;
+        };
+        match &variant.fields {
+            Fields::Unit => quote! {
+                if command.is(<#enum_ty>::#selector_name) {
+                    #switch
+                }
+            },
+            Fields::Unnamed(fields) if fields.unnamed.is_empty() => quote! {
+                if command.is(<#enum_ty>::#selector_name) {
+                    #switch
+                }
+            },
+            _ => quote! {
+                if let Some(value) = command.get(<#enum_ty>::#selector_name) {
+                    let value = value.to_owned();
+                    #switch
+                }
+            },
+        }
+    });
+
+    let selector_handling = if input.selectors {
+        quote! {
+            if let ::druid::Event::Command(command) = event {
+                #(#selector_checks)*
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let slot_match = input.variants.iter().enumerate().map(|(index, variant)| {
+        let builder_name = variant.resolve_builder_name();
+        let factory_name = factory_name(variant);
+        let variant_name = &variant.name;
+        quote! {
+            #enum_name::#variant_name { .. }
+                if self.#builder_name.is_some() || self.#factory_name.is_some() =>
+            {
+                Some(#index)
+            }
+        }
+    });
+
+    let enter_match = input.variants.iter().map(|variant| {
+        let builder_name = variant.resolve_builder_name();
+        let factory_name = factory_name(variant);
+        let variant_name = &variant.name;
+        quote! {
+            #enum_name::#variant_name { .. } => {
+                if self.#builder_name.is_none() {
+                    if let Some(build) = &mut self.#factory_name {
+                        self.#builder_name = Some(::druid::WidgetPod::new(build()));
+                    }
+                }
+            }
+        }
+    });
+
+    let exit_match = input.variants.iter().enumerate().map(|(index, variant)| {
+        let builder_name = variant.resolve_builder_name();
+        let factory_name = factory_name(variant);
+        let data_name = data_name(variant);
+        let variant_name = &variant.name;
+        let drop_data = quote! {
+            if !self.keep_alive_ {
+                self.#data_name = None;
+            }
+        };
+        if variant.rebuild_on_enter {
+            quote! {
+                #enum_name::#variant_name { .. } => if self.#factory_name.is_some() {
+                    self.#builder_name = None;
+                    self.#data_name = None;
+                    self.added_[#index] = false;
+                } else #drop_data
+            }
+        } else {
+            quote!(#enum_name::#variant_name { .. } => #drop_data)
+        }
+    });
+
+    let background_event = input.variants.iter().enumerate().map(|(index, variant)| {
+        let builder_name = variant.resolve_builder_name();
+        let data_name = data_name(variant);
+        quote! {
+            if self.hidden_(&#enum_data, Some(#index)) && self.added_[#index] {
+                if let (Some(widget), Some(d)) = (&mut self.#builder_name, &mut self.#data_name) {
+                    #refresh_shared
+                    widget.event(ctx, event, d, env);
+                    #write_back_shared
+                }
+            }
+        }
+    });
+
+    let background_lifecycle = input.variants.iter().enumerate().map(|(index, variant)| {
+        let builder_name = variant.resolve_builder_name();
+        let data_name = data_name(variant);
+        quote! {
+            if self.hidden_(&#enum_data, Some(#index)) && self.added_[#index] {
+                if let (Some(widget), Some(d)) = (&mut self.#builder_name, &mut self.#data_name) {
+                    #refresh_shared
+                    widget.lifecycle(ctx, event, d, env);
+                }
+            }
+        }
+    });
+
+    // Without shared data the hidden widgets' data can't change.
+    let background_update = input
+        .variants
+        .iter()
+        .enumerate()
+        .filter(|_| shared.is_some())
+        .map(|(index, variant)| {
+            let builder_name = variant.resolve_builder_name();
+            let data_name = data_name(variant);
+            quote! {
+                if self.hidden_(&#enum_data, Some(#index)) && self.added_[#index] {
+                    if let (Some(widget), Some(d)) = (&mut self.#builder_name, &mut self.#data_name) {
+                        #refresh_shared
+                        widget.update(ctx, d, env);
+                    }
+                }
+            }
+        });
+    let variant_count = input.variants.len();
+
+    let widget_generics = widget_generics(input, &matcher_generics);
+    let (_, _, widget_where_clause) = widget_generics.split_for_impl();
+
+    let trait_impl = match shared {
+        Some(shared) => quote! {
+            impl #impl_generics ::druid_enums::matcher::SharedMatcher<#shared> for #enum_ty #widget_where_clause {
+                type SharedMatcher = #matcher_name #ty_generics;
+                fn shared_matcher() -> Self::SharedMatcher {
+                    #matcher_name::new()
+                }
+            }
+        },
+        None => quote! {
+            impl #impl_generics ::druid_enums::matcher::Matcher for #enum_ty #widget_where_clause {
+                type Matcher = #matcher_name #ty_generics;
+                fn matcher() -> Self::Matcher {
+                    #matcher_name::new()
+                }
+            }
+        },
+    };
+
+    quote! {
+        #visibility struct #matcher_name #matcher_generics #where_clause {
+            #(#struct_fields,)*
+            default_: Option<::druid::WidgetPod<#data_ty, Box<dyn ::druid::Widget<#data_ty>>>>,
+            discriminant_: Option<::std::mem::Discriminant<#enum_ty>>,
+            added_: [bool; #variant_count],
+            default_added_: bool,
+            keep_alive_: bool,
+            missing_: ::druid_enums::Missing,
+            enter_hooks_: Vec<Box<dyn FnMut(&mut ::druid::UpdateCtx, &#data_ty, &::druid::Env)>>,
+            exit_hooks_: Vec<Box<dyn FnMut(&mut ::druid::UpdateCtx, &#data_ty, &::druid::Env)>>,
+            change_hooks_: Vec<Box<dyn FnMut(&mut ::druid::UpdateCtx, &#data_ty, &#data_ty, &::druid::Env)>>,
+            animation_: ::druid_enums::transition::Animation<#data_ty>,
+            // The `Env` of the last `update`, to tell whether it changed.
+            env_: Option<::druid::Env>,
+        }
+
+        impl #impl_generics #matcher_name #ty_generics #where_clause {
+            pub fn new() -> Self {
+                Self {
+                    #(#struct_defaults,)*
+                    default_: None,
+                    discriminant_: None,
+                    added_: [false; #variant_count],
+                    default_added_: false,
+                    keep_alive_: false,
+                    missing_: ::druid_enums::Missing::default(),
+                    enter_hooks_: Vec::new(),
+                    exit_hooks_: Vec::new(),
+                    change_hooks_: Vec::new(),
+                    animation_: ::druid_enums::transition::Animation::new(::druid_enums::Transition::None),
+                    env_: None,
+                }
+            }
+            pub fn default(mut self, widget: impl ::druid::Widget<#data_ty> + 'static) -> Self {
+                self.default_ = Some(::druid::WidgetPod::new(Box::new(widget)));
+                self
+            }
+            pub fn default_empty(mut self) -> Self where #data_ty: ::druid::Data {
+                self.default_ = Some(::druid::WidgetPod::new(Box::new(::druid::widget::SizedBox::empty())));
+                self
+            }
+            /// Keeps the widgets of hidden variants alive.
+            ///
+            /// Once shown, the widget of a variant keeps receiving commands and timers while
+            /// another variant is shown, with the data its variant had when it was left. Input,
+            /// layout and paint still only go to the shown widget. Variants that are rebuilt on
+            /// entry are dropped when left regardless.
+            pub fn keep_alive(mut self) -> Self {
+                self.keep_alive_ = true;
+                self
+            }
+            /// Sets what to do about variants with neither a widget nor a default.
+            ///
+            /// Defaults to [`Missing::Panic`](::druid_enums::Missing::Panic) in debug builds and
+            /// [`Missing::Warn`](::druid_enums::Missing::Warn) otherwise.
+            pub fn on_missing(mut self, missing: ::druid_enums::Missing) -> Self {
+                self.missing_ = missing;
+                self
+            }
+            /// Calls `hook` each time the variant `prism` focuses on is switched to, with the data
+            /// its widget sees.
+            ///
+            /// It isn't called for the variant the matcher starts with, which isn't switched to.
+            /// `UpdateCtx` can't request focus, submit a command to a widget that does it in
+            /// `event` instead.
+            pub fn on_enter<V: ::druid::Data>(
+                mut self,
+                prism: impl ::druid_enums::Prism<#enum_ty, V> + 'static,
+                mut hook: impl FnMut(&mut ::druid::UpdateCtx, &#hook_data_ty, &::druid::Env) + 'static,
+            ) -> Self {
+                self.enter_hooks_.push(Box::new(move |ctx, data, env| {
+                    prism.with(&#enum_data, |value| hook(ctx, &#hook_data, env));
+                }));
+                self
+            }
+            /// Calls `hook` each time the variant `prism` focuses on is switched away from, with the
+            /// data its widget saw last.
+            pub fn on_exit<V: ::druid::Data>(
+                mut self,
+                prism: impl ::druid_enums::Prism<#enum_ty, V> + 'static,
+                mut hook: impl FnMut(&mut ::druid::UpdateCtx, &#hook_data_ty, &::druid::Env) + 'static,
+            ) -> Self {
+                self.exit_hooks_.push(Box::new(move |ctx, data, env| {
+                    prism.with(&#enum_data, |value| hook(ctx, &#hook_data, env));
+                }));
+                self
+            }
+            /// Calls `hook` with the old and the new data each time the variant changes, after any
+            /// `on_exit` and `on_enter` hooks.
+            pub fn on_change(
+                mut self,
+                hook: impl FnMut(&mut ::druid::UpdateCtx, &#data_ty, &#data_ty, &::druid::Env) + 'static,
+            ) -> Self {
+                self.change_hooks_.push(Box::new(hook));
+                self
+            }
+            /// Animates switching from one variant to another with `transition`.
+            pub fn transition(mut self, transition: ::druid_enums::Transition) -> Self {
+                self.animation_ = ::druid_enums::transition::Animation::new(transition);
+                self
+            }
+            #(#builder_fns)*
+
+            // The index of the variant of `data` if it has a widget of its own, `None` if it's
+            // shown by the default widget.
+            fn slot_(&self, data: &#enum_ty) -> Option<usize> {
+                match data {
+                    #(#slot_match,)*
+                    _ => None,
+                }
+            }
+
+            // Whether the widget showing `data` has received `WidgetAdded`.
+            //
+            // A widget only gets other passes after that, and a widget that was shown before keeps
+            // its state while hidden and catches up on the data in `update` once it's shown again.
+            fn added_(&mut self, data: &#enum_ty) -> &mut bool {
+                match self.slot_(data) {
+                    Some(index) => &mut self.added_[index],
+                    None => &mut self.default_added_,
+                }
+            }
+
+            // Whether the widget in `slot` is neither showing `data` nor animating out.
+            fn hidden_(&self, data: &#enum_ty, slot: Option<usize>) -> bool {
+                self.slot_(data) != slot
+                    && self
+                        .animation_
+                        .outgoing()
+                        .map_or(true, |outgoing| self.slot_(&#outgoing_enum) != slot)
+            }
+
+            // Builds the widget for `data` if it's built lazily and doesn't exist yet.
+            fn enter_(&mut self, data: &#enum_ty) {
+                match data {
+                    #(#enter_match)*
+                }
+            }
+        }
+
+        impl #impl_generics #matcher_name #ty_generics #widget_where_clause {
+            // Drops the widget for `data` if it's rebuilt every time its variant is shown, and
+            // the data it saw unless it's kept alive.
+            fn exit_(&mut self, data: &#enum_ty) {
+                match data {
+                    #(#exit_match,)*
+                }
+            }
+
+            // Writes back the parts of the data the widget showing `data` changed, leaving the
+            // others as they are so they stay `same`.
+            fn write_back_(&self, data: &mut #data_ty) {
+                match &mut #enum_data {
+                    #(#write_back_match)*
+                }
+            }
+
+            // Whether the shared data and the variant's data are the same in both, compared field
+            // by field like the `Data` impl of the variant's view struct does.
+            fn same_(&self, old_data: &#data_ty, data: &#data_ty) -> bool {
+                #same_shared match (&#old_enum_data, &#enum_data) {
+                    #(#same_match)*
+                    _ => false,
+                }
+            }
+
+            // Brings the data the widget showing `data` sees up to date, only cloning the parts
+            // that changed since the last pass.
+            fn project_(&mut self, data: &#data_ty) {
+                if self.slot_(&#enum_data).is_none() {
+                    return;
+                }
+                match &#enum_data {
+                    #(#project_match)*
+                }
+            }
+
+            fn paint_data_(&mut self, ctx: &mut ::druid::PaintCtx, data: &#data_ty, env: &::druid::Env) {
+                if !*self.added_(&#enum_data) {
+                    return;
+                }
+                self.project_(data);
+                match #enum_data {
+                    #(#paint_match)*
+                }
+            }
+
+            // Sends commands and timers to the widgets of hidden variants that are kept alive.
+            fn background_event_(
+                &mut self,
+                ctx: &mut ::druid::EventCtx,
+                event: &::druid::Event,
+                data: &mut #data_ty,
+                env: &::druid::Env,
+            ) {
+                #(#background_event)*
+                if self.hidden_(&#enum_data, None) && self.default_added_ {
+                    if let Some(default) = &mut self.default_ {
+                        default.event(ctx, event, data, env);
+                    }
+                }
+            }
+
+            fn background_lifecycle_(
+                &mut self,
+                ctx: &mut ::druid::LifeCycleCtx,
+                event: &::druid::LifeCycle,
+                data: &#data_ty,
+                env: &::druid::Env,
+            ) {
+                #(#background_lifecycle)*
+                if self.hidden_(&#enum_data, None) && self.default_added_ {
+                    if let Some(default) = &mut self.default_ {
+                        default.lifecycle(ctx, event, data, env);
+                    }
+                }
+            }
+
+            fn background_update_(
+                &mut self,
+                ctx: &mut ::druid::UpdateCtx,
+                data: &#data_ty,
+                env: &::druid::Env,
+            ) {
+                #(#background_update)*
+                if self.hidden_(&#enum_data, None) && self.default_added_ {
+                    if let Some(default) = &mut self.default_ {
+                        default.update(ctx, data, env);
+                    }
+                }
+            }
+        }
+
+        impl #impl_generics ::druid::Widget<#data_ty> for #matcher_name #ty_generics #widget_where_clause {
+            fn event(
+                &mut self,
+                ctx: &mut ::druid::EventCtx,
+                event: &::druid::Event,
+                data: &mut #data_ty,
+                env: &::druid::Env
+            ) {
+                #selector_handling
+                if self.discriminant_ == Some(::std::mem::discriminant(&#enum_data))
+                    && *self.added_(&#enum_data)
+                {
+                    self.project_(data);
+                    match #enum_data {
+                        #(#event_match)*
+                    }
+                }
+                if self.keep_alive_ {
+                    match event {
+                        ::druid::Event::Command(_)
+                        | ::druid::Event::Internal(::druid::InternalEvent::TargetedCommand(..))
+                        | ::druid::Event::Internal(::druid::InternalEvent::RouteTimer(..)) => {
+                            self.background_event_(ctx, event, data, env);
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            fn lifecycle(
+                &mut self,
+                ctx: &mut ::druid::LifeCycleCtx,
+                event: &::druid::LifeCycle,
+                data: &#data_ty,
+                env: &::druid::Env
+            ) {
+                if let ::druid::LifeCycle::WidgetAdded = event {
+                    self.discriminant_ = Some(::std::mem::discriminant(&#enum_data));
+                    #(#widget_added_checks)*
+                }
+                if let ::druid::LifeCycle::AnimFrame(nanos) = event {
+                    if let Some(outgoing) = self.animation_.advance(*nanos) {
+                        self.exit_(&#outgoing_enum);
+                        ctx.request_paint();
+                    } else if self.animation_.outgoing().is_some() {
+                        ctx.request_anim_frame();
+                    }
+                }
+                if self.keep_alive_ {
+                    if let ::druid::LifeCycle::Internal(_) | ::druid::LifeCycle::AnimFrame(_) = event {
+                        self.background_lifecycle_(ctx, event, data, env);
+                    }
+                }
+                if !*self.added_(&#enum_data) {
+                    match event {
+                        ::druid::LifeCycle::WidgetAdded
+                        | ::druid::LifeCycle::Internal(::druid::InternalLifeCycle::RouteWidgetAdded) => {
+                            self.enter_(&#enum_data);
+                            *self.added_(&#enum_data) = true;
+                        }
+                        _ => return,
+                    }
+                }
+                self.project_(data);
+                match #enum_data {
+                    #(#lifecycle_match)*
+                }
+            }
+            fn update(&mut self,
+                ctx: &mut ::druid::UpdateCtx,
+                old_data: &#data_ty,
+                data: &#data_ty,
+                env: &::druid::Env
+            ) {
+                let env_same = self.env_.as_ref().map_or(false, |old_env| ::druid::Data::same(old_env, env));
+                self.env_ = Some(env.clone());
+                let discriminant = ::std::mem::discriminant(&#enum_data);
+                if ::std::mem::discriminant(&#old_enum_data) != discriminant {
+                    // The newly shown widget gets `WidgetAdded` in the lifecycle pass following
+                    // this one if it hasn't been added yet.
+                    self.discriminant_ = Some(discriminant);
+                    if let Some(outgoing) = self.animation_.stop() {
+                        self.exit_(&#outgoing_enum);
+                    }
+                    // The widget that was shown is only left once it has animated out.
+                    if self.animation_.is_animated() && *self.added_(&#old_enum_data) {
+                        self.animation_.start(old_data.to_owned());
+                        ctx.request_anim_frame();
+                    } else {
+                        self.exit_(&#old_enum_data);
+                    }
+                    for hook in &mut self.exit_hooks_ {
+                        hook(ctx, old_data, env);
+                    }
+                    for hook in &mut self.enter_hooks_ {
+                        hook(ctx, data, env);
+                    }
+                    for hook in &mut self.change_hooks_ {
+                        hook(ctx, old_data, data, env);
+                    }
+                    ctx.children_changed();
+                } else if env_same && self.same_(old_data, data) {
+                    // Nothing any of the widgets see has changed.
+                    return;
+                }
+                if *self.added_(&#enum_data) {
+                    self.project_(data);
+                    match #enum_data {
+                        #(#update_match)*
+                    }
+                }
+                if self.keep_alive_ {
+                    self.background_update_(ctx, data, env);
+                }
+            }
+            fn layout(
+                &mut self,
+                ctx: &mut ::druid::LayoutCtx,
+                bc: &::druid::BoxConstraints,
+                data: &#data_ty,
+                env: &::druid::Env
+            ) -> ::druid::Size {
+                if !*self.added_(&#enum_data) {
+                    return bc.min();
+                }
+                self.project_(data);
+                match #enum_data {
+                    #(#layout_match)*
+                }
+            }
+            fn paint(&mut self, ctx: &mut ::druid::PaintCtx, data: &#data_ty, env: &::druid::Env) {
+                ::druid_enums::transition::Animation::paint(
+                    self,
+                    ctx,
+                    data,
+                    env,
+                    |matcher| &mut matcher.animation_,
+                    Self::paint_data_,
+                );
+            }
+        }
+
+        #trait_impl
+    }
+}
+
+/// The field holding the function that builds the widget of a lazily built variant.
+fn factory_name(variant: &MatcherVariant) -> Ident {
+    format_ident!("{}_factory_", variant.resolve_base_name())
+}
+
+/// The field holding the data the widget of a variant sees, kept between passes so it's only
+/// cloned from the enum when it changes. A widget that is kept alive keeps seeing the data its
+/// variant had when it was left.
+fn data_name(variant: &MatcherVariant) -> Ident {
+    format_ident!("{}_data_", variant.resolve_base_name())
+}
+
+/// Adds the bounds a matcher needs to implement `Widget` to `generics`: every type parameter of the
+/// enum and the enum itself are `Data`.
+pub fn widget_generics(input: &MatcherDerive, generics: &Generics) -> Generics {
+    let enum_name = &input.enum_name;
+    let (_, enum_ty_generics, _) = input.generics.split_for_impl();
+    let mut generics = generics.clone();
+    let predicates = &mut generics.make_where_clause().predicates;
+    for param in input.generics.type_params() {
+        let param = &param.ident;
+        predicates.push(parse_quote!(#param: ::druid::Data));
+    }
+    predicates.push(parse_quote!(#enum_name #enum_ty_generics: ::druid::Data));
+    generics
+}
+
+/// Inserts the `Shared` parameter of the matcher after the lifetimes of the enum's own generics.
+pub fn matcher_generics(generics: &Generics, shared: &Ident) -> Generics {
+    let mut generics = generics.clone();
+    let position = generics
+        .params
+        .iter()
+        .take_while(|param| matches!(param, GenericParam::Lifetime(_)))
+        .count();
+    generics
+        .params
+        .insert(position, parse_quote!(#shared: ::druid::Data));
+    generics
+}
+
+/// Returns the `T` in `Widget<T>` for the variant.
+pub fn type_of(variant: &MatcherVariant, generics: &Generics) -> TokenStream {
+    match &variant.fields {
+        Fields::Unit => quote!(()),
+        Fields::Unnamed(fields) if fields.unnamed.is_empty() => quote!(()),
+        Fields::Unnamed(fields) => {
+            let types = fields.unnamed.iter().map(|f| &f.ty);
+            quote!((#(#types),*))
+        }
+        Fields::Named(fields) => {
+            let view_name = variant.resolve_view_name();
+            let view_generics = view::view_generics(generics, fields);
+            let (_, view_ty_generics, _) = view_generics.split_for_impl();
+            quote!(#view_name #view_ty_generics)
+        }
+    }
+}
+
+/// Returns (pattern to match for, `data` param for the widget, expression rebuilding the variant
+/// from the widget's data in `variant_data`).
+pub fn data_of(
+    enum_name: &Ident,
+    variant: &MatcherVariant,
+    prefix: &str,
+    variant_data: &TokenStream,
+) -> (TokenStream, TokenStream, TokenStream) {
+    let variant_name = &variant.name;
+    let names = |len: usize| -> Vec<Ident> {
+        (0..len)
+            .map(|i| format_ident!("{}p{}", prefix, i))
+            .collect()
+    };
+    match &variant.fields {
+        Fields::Unit => (quote!(), quote!(()), quote!(#enum_name::#variant_name)),
+        Fields::Unnamed(fields) if fields.unnamed.is_empty() => {
+            (quote!(()), quote!(()), quote!(#enum_name::#variant_name()))
+        }
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            let name = &names(1)[0];
+            (
+                quote!((#name)),
+                quote!(#name.to_owned()),
+                quote!(#enum_name::#variant_name(#variant_data)),
+            )
+        }
+        Fields::Unnamed(fields) => {
+            let names = names(fields.unnamed.len());
+            let indices = (0..names.len()).map(syn::Index::from);
+            (
+                quote!((#(#names),*)),
+                quote!((#(#names.to_owned()),*)),
+                quote!(#enum_name::#variant_name(#(#variant_data.#indices),*)),
+            )
+        }
+        Fields::Named(fields) => {
+            let view_name = variant.resolve_view_name();
+            let fields: Vec<&Ident> = fields.named.iter().flat_map(|f| &f.ident).collect();
+            let names = names(fields.len());
+            (
+                quote!({ #(#fields: #names),* }),
+                quote!(#view_name { #(#fields: #names.to_owned()),* }),
+                quote!(#enum_name::#variant_name { #(#fields: #variant_data.#fields),* }),
+            )
+        }
+    }
+}
+
+/// The places of the fields of `variant` in the data its widget sees, given the place of that data
+/// in `variant_data`, each with the name `data_of` binds the field to with `prefix`.
+pub fn fields_of(
+    variant: &MatcherVariant,
+    prefix: &str,
+    variant_data: &TokenStream,
+) -> Vec<(TokenStream, Ident)> {
+    let name = |i: usize| format_ident!("{}p{}", prefix, i);
+    match &variant.fields {
+        Fields::Unit => Vec::new(),
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            vec![(variant_data.clone(), name(0))]
+        }
+        Fields::Unnamed(fields) => (0..fields.unnamed.len())
+            .map(|i| {
+                let index = syn::Index::from(i);
+                (quote!(#variant_data.#index), name(i))
+            })
+            .collect(),
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .flat_map(|field| &field.ident)
+            .enumerate()
+            .map(|(i, field)| (quote!(#variant_data.#field), name(i)))
+            .collect(),
+    }
+}
+
+/// How each of the fields `fields_of` returns is compared, from its `#[data(...)]` attributes.
+///
+/// That's how the variant's view struct, or druid's derive on the enum, compares them. Those also
+/// report any errors in the attributes, so fields with such errors are compared as `Data` here.
+pub fn same_fns(variant: &MatcherVariant) -> Vec<SameFn> {
+    variant
+        .fields
+        .iter()
+        .map(|field| view::same_fn(field).unwrap_or(SameFn::Data))
+        .collect()
+}
+
+/// Brings the field `cache` of the data a variant widget sees up to date with the reference to it
+/// in `data`, unless `same_fn` says it didn't change.
+///
+/// Ignored fields can't be compared, so they're always copied.
+fn refresh_field(same_fn: &SameFn, cache: &TokenStream, data: &Ident) -> TokenStream {
+    match same_fn {
+        SameFn::Data => quote!(::druid_enums::matcher::refresh(&mut #cache, #data);),
+        SameFn::Path(path) => {
+            quote!(::druid_enums::matcher::refresh_by(&mut #cache, #data, #path);)
+        }
+        SameFn::Ignore => quote!(::std::clone::Clone::clone_from(&mut #cache, #data);),
+    }
+}
+
+/// Writes the field `cache` of the data a variant widget saw back to the `&mut` to it in `data`,
+/// unless `same_fn` says it didn't change.
+///
+/// Ignored fields can't be compared, so they're always written back.
+pub fn write_back_field(same_fn: &SameFn, cache: &TokenStream, data: &Ident) -> TokenStream {
+    match same_fn {
+        SameFn::Data => quote!(::druid_enums::matcher::write_back(&#cache, #data);),
+        SameFn::Path(path) => {
+            quote!(::druid_enums::matcher::write_back_by(&#cache, #data, #path);)
+        }
+        SameFn::Ignore => quote!(::std::clone::Clone::clone_from(#data, &#cache);),
+    }
+}
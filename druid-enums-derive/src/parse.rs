@@ -0,0 +1,646 @@
+use heck::SnakeCase;
+use proc_macro2::Span;
+use quote::format_ident;
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, Data, DataStruct, DataUnion, DeriveInput, Error, Expr, Fields, Generics, Ident,
+    LitStr, Path, Result, Token, Visibility,
+};
+
+use crate::exhaustive::unchecked_name;
+use crate::prism::prism_name;
+use crate::selector::selector_name;
+
+pub struct MatcherDerive {
+    pub enum_name: Ident,
+    pub visibility: Visibility,
+    pub matcher_name: Option<Ident>,
+    pub shared_matcher_name: Option<Ident>,
+    /// Whether the matchers are typestate builders that only implement `Widget` once every
+    /// variant has a widget.
+    pub exhaustive: bool,
+    /// Whether to generate a `Selector` for each variant, which the matchers handle by switching
+    /// to that variant.
+    pub selectors: bool,
+    /// Whether to generate `is_*`, `as_*`, `as_*_mut` and `into_*` for each variant.
+    pub accessors: bool,
+    pub generics: Generics,
+    pub variants: Vec<MatcherVariant>,
+}
+
+impl MatcherDerive {
+    pub fn resolve_matcher_name(&self) -> Ident {
+        self.matcher_name.as_ref().cloned().unwrap_or_else(|| {
+            // TODO at least think about how this name is constructed, make it a concious choice.
+            Ident::new(&format!("{}Matcher", self.enum_name), self.enum_name.span())
+        })
+    }
+
+    pub fn resolve_shared_matcher_name(&self) -> Ident {
+        self.shared_matcher_name
+            .as_ref()
+            .cloned()
+            .unwrap_or_else(|| {
+                Ident::new(
+                    &format!("{}SharedMatcher", self.enum_name),
+                    self.enum_name.span(),
+                )
+            })
+    }
+
+    /// The name of the generic parameter for the shared data, chosen so it doesn't collide with
+    /// any of the enum's own generic parameters.
+    pub fn resolve_shared_ident(&self) -> Ident {
+        self.fresh_ident("Shared", &[])
+    }
+
+    /// The name of the generic parameter of an exhaustive matcher tracking whether `variant` has
+    /// a widget.
+    pub fn resolve_state_ident(&self, variant: &MatcherVariant) -> Ident {
+        let index = self
+            .variants
+            .iter()
+            .position(|other| other.name == variant.name)
+            .expect("variant of another enum");
+        self.state_idents().swap_remove(index)
+    }
+
+    /// The name of the generic parameter of an exhaustive matcher tracking whether it has a
+    /// default widget.
+    pub fn resolve_default_state_ident(&self) -> Ident {
+        self.state_idents().pop().unwrap()
+    }
+
+    /// The state parameters of an exhaustive matcher, one for each variant followed by the one
+    /// for the default widget, chosen so they don't collide with each other, the shared data's
+    /// parameter or the enum's own parameters.
+    fn state_idents(&self) -> Vec<Ident> {
+        let mut taken = vec![self.resolve_shared_ident()];
+        let bases = self
+            .variants
+            .iter()
+            .map(|variant| format!("{}_", variant.name.unraw()))
+            .chain(std::iter::once(String::from("Default_")));
+        for base in bases {
+            let ident = self.fresh_ident(&base, &taken);
+            taken.push(ident);
+        }
+        taken.split_off(1)
+    }
+
+    /// `base`, with underscores appended while it collides with a generic parameter of the enum
+    /// or any of `taken`.
+    fn fresh_ident(&self, base: &str, taken: &[Ident]) -> Ident {
+        let mut name = String::from(base);
+        while self.generics.type_params().any(|param| param.ident == name)
+            || self
+                .generics
+                .const_params()
+                .any(|param| param.ident == name)
+            || taken.iter().any(|ident| ident == &name)
+        {
+            name.push('_');
+        }
+        Ident::new(&name, Span::call_site())
+    }
+}
+
+impl Parse for MatcherDerive {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let input: DeriveInput = input.parse()?;
+        let enum_name = input.ident;
+        let visibility = input.vis;
+        let generics = input.generics;
+        let data = match input.data {
+            Data::Enum(data) => Ok(data),
+            Data::Struct(DataStruct { struct_token, .. }) => enum_error(struct_token.span),
+            Data::Union(DataUnion { union_token, .. }) => enum_error(union_token.span),
+        }?;
+        let mut matcher_name = None;
+        let mut shared_matcher_name = None;
+        let mut rebuild_on_enter = false;
+        let mut exhaustive = false;
+        let mut selectors = None;
+        let mut accessors = false;
+        for attr in process_attrs(input.attrs) {
+            match attr? {
+                MatcherAttr::BuilderName(_, span)
+                | MatcherAttr::ViewName(_, span)
+                | MatcherAttr::Label(_, span)
+                | MatcherAttr::Default(span)
+                | MatcherAttr::DefaultData(_, span) => {
+                    return Err(Error::new(span, "attribute not valid on enum"))
+                }
+                MatcherAttr::MatcherName(name, _) => matcher_name = Some(name),
+                MatcherAttr::SharedMatcherName(name, _) => shared_matcher_name = Some(name),
+                MatcherAttr::RebuildOnEnter => rebuild_on_enter = true,
+                MatcherAttr::Exhaustive(_) => exhaustive = true,
+                MatcherAttr::Selectors(span) => selectors = Some(span),
+                MatcherAttr::Accessors(_) => accessors = true,
+            }
+        }
+        // Selectors are keyed by name, so every instance of a generic enum would share them and a
+        // matcher would panic getting a payload of another instance.
+        if let Some(span) = selectors {
+            if generics.type_params().next().is_some() || generics.const_params().next().is_some() {
+                return Err(Error::new(
+                    span,
+                    "selectors can't tell the instances of a generic enum apart",
+                ));
+            }
+        }
+        let mut variants = Vec::new();
+        for variant in data.variants {
+            let variant_name = variant.ident;
+            let attrs = VariantAttrs::parse(variant.attrs)?;
+            match (attrs.view_name_span, &variant.fields) {
+                (Some(span), Fields::Unit) | (Some(span), Fields::Unnamed(_)) => {
+                    return Err(Error::new(
+                        span,
+                        "attribute only valid on variants with named fields",
+                    ))
+                }
+                _ => (),
+            }
+            if let (Some(span), Fields::Unit) = (attrs.default_data_span, &variant.fields) {
+                return Err(Error::new(
+                    span,
+                    "unit variants always have a default, the attribute isn't needed",
+                ));
+            }
+            variants.push(MatcherVariant {
+                builder_name: attrs.builder_name,
+                view_name: attrs.view_name,
+                label: attrs.label,
+                default_data: attrs.default_data,
+                rebuild_on_enter: rebuild_on_enter || attrs.rebuild_on_enter,
+                name: variant_name,
+                fields: variant.fields,
+            });
+        }
+        let input = MatcherDerive {
+            enum_name,
+            visibility,
+            matcher_name,
+            shared_matcher_name,
+            exhaustive,
+            selectors: selectors.is_some(),
+            accessors,
+            generics,
+            variants,
+        };
+        check_names(&input)?;
+        Ok(input)
+    }
+}
+
+fn enum_error<T>(span: Span) -> Result<T> {
+    Err(Error::new(span, "only `enum`s can implement `Matcher`"))
+}
+
+pub struct MatcherVariant {
+    pub builder_name: Option<Ident>,
+    pub view_name: Option<Ident>,
+    pub label: Option<LitStr>,
+    /// How to build the variant without being given its data, see `Matchable::default_for`.
+    pub default_data: Option<DefaultData>,
+    /// Whether a lazily built widget is dropped when the variant is left.
+    pub rebuild_on_enter: bool,
+    pub name: Ident,
+    pub fields: Fields,
+}
+
+impl MatcherVariant {
+    pub fn resolve_builder_name(&self) -> Ident {
+        self.builder_name
+            .as_ref()
+            .cloned()
+            .unwrap_or_else(|| snakify(&self.name))
+    }
+
+    /// The builder name without `r#` or the `_` keywords that can't be raw get, which the other
+    /// names generated for the variant start with.
+    pub fn resolve_base_name(&self) -> String {
+        match &self.builder_name {
+            Some(name) => name.unraw().to_string(),
+            None => self.name.unraw().to_string().to_snake_case(),
+        }
+    }
+
+    /// The name of the function call to build the corresponding widget lazily.
+    pub fn resolve_lazy_builder_name(&self) -> Ident {
+        format_ident!("{}_with", self.resolve_base_name(), span = self.name.span())
+    }
+
+    /// The name of the struct generated for the fields of a struct-like variant, starting with an
+    /// upper case letter even if the variant doesn't.
+    pub fn resolve_view_name(&self) -> Ident {
+        self.view_name.as_ref().cloned().unwrap_or_else(|| {
+            let name = self.name.unraw().to_string();
+            let mut chars = name.chars();
+            let first = chars.next().into_iter().flat_map(char::to_uppercase);
+            let name: String = first.chain(chars).collect();
+            format_ident!("{}View", name, span = self.name.span())
+        })
+    }
+
+    /// The text shown for the variant in generated UI like tabs.
+    pub fn resolve_label(&self) -> String {
+        self.label
+            .as_ref()
+            .map(LitStr::value)
+            .unwrap_or_else(|| self.name.unraw().to_string())
+    }
+}
+
+/// The data a variant gets when it's built without being given any.
+pub enum DefaultData {
+    /// `Default::default()` for each field.
+    Default,
+    /// An expression of the type the variant's widget sees.
+    Expr(Box<Expr>),
+}
+
+#[derive(Default)]
+struct VariantAttrs {
+    /// The name of the function call to build the corresponding widget.
+    builder_name: Option<Ident>,
+    /// The name of the struct generated for a variant with named fields.
+    view_name: Option<Ident>,
+    view_name_span: Option<Span>,
+    /// The text shown for the variant in generated UI like tabs.
+    label: Option<LitStr>,
+    default_data: Option<DefaultData>,
+    default_data_span: Option<Span>,
+    /// Whether to build the widget again each time the variant is entered.
+    rebuild_on_enter: bool,
+}
+
+impl VariantAttrs {
+    fn parse(input: Vec<Attribute>) -> Result<Self> {
+        let mut matcher_attrs = VariantAttrs::default();
+        for attr in process_attrs(input) {
+            match attr? {
+                MatcherAttr::BuilderName(builder_name, _) => {
+                    matcher_attrs.builder_name = Some(builder_name)
+                }
+                MatcherAttr::ViewName(view_name, span) => {
+                    matcher_attrs.view_name = Some(view_name);
+                    matcher_attrs.view_name_span = Some(span);
+                }
+                MatcherAttr::Label(label, _) => matcher_attrs.label = Some(label),
+                MatcherAttr::Default(span) => {
+                    matcher_attrs.default_data = Some(DefaultData::Default);
+                    matcher_attrs.default_data_span = Some(span);
+                }
+                MatcherAttr::DefaultData(expr, span) => {
+                    matcher_attrs.default_data = Some(DefaultData::Expr(expr));
+                    matcher_attrs.default_data_span = Some(span);
+                }
+                MatcherAttr::RebuildOnEnter => matcher_attrs.rebuild_on_enter = true,
+                MatcherAttr::MatcherName(_, span)
+                | MatcherAttr::SharedMatcherName(_, span)
+                | MatcherAttr::Exhaustive(span)
+                | MatcherAttr::Selectors(span)
+                | MatcherAttr::Accessors(span) => {
+                    return Err(Error::new(span, "attribute not valid on variants"))
+                }
+            }
+        }
+        Ok(matcher_attrs)
+    }
+}
+
+// spans are for error reporting.
+enum MatcherAttr {
+    MatcherName(Ident, Span),
+    SharedMatcherName(Ident, Span),
+    BuilderName(Ident, Span),
+    ViewName(Ident, Span),
+    Label(LitStr, Span),
+    Default(Span),
+    DefaultData(Box<Expr>, Span),
+    RebuildOnEnter,
+    Exhaustive(Span),
+    Selectors(Span),
+    Accessors(Span),
+}
+
+impl Parse for MatcherAttr {
+    fn parse(s: ParseStream) -> Result<Self> {
+        let attr_name = s.parse::<Ident>()?;
+        let name_span = attr_name.span();
+        match attr_name.to_string().as_str() {
+            "builder_name" => {
+                s.parse::<Token![=]>()?;
+                s.parse()
+                    .map(|builder_name| MatcherAttr::BuilderName(builder_name, name_span))
+            }
+            "matcher_name" => {
+                s.parse::<Token![=]>()?;
+                s.parse()
+                    .map(|matcher_name| MatcherAttr::MatcherName(matcher_name, name_span))
+            }
+            "shared_matcher_name" => {
+                s.parse::<Token![=]>()?;
+                s.parse()
+                    .map(|name| MatcherAttr::SharedMatcherName(name, name_span))
+            }
+            "view_name" => {
+                s.parse::<Token![=]>()?;
+                s.parse()
+                    .map(|view_name| MatcherAttr::ViewName(view_name, name_span))
+            }
+            "label" => {
+                s.parse::<Token![=]>()?;
+                s.parse().map(|label| MatcherAttr::Label(label, name_span))
+            }
+            "default" => Ok(MatcherAttr::Default(name_span)),
+            "default_data" => {
+                s.parse::<Token![=]>()?;
+                s.parse()
+                    .map(|expr| MatcherAttr::DefaultData(expr, name_span))
+            }
+            "rebuild_on_enter" => Ok(MatcherAttr::RebuildOnEnter),
+            "exhaustive" => Ok(MatcherAttr::Exhaustive(name_span)),
+            "selectors" => Ok(MatcherAttr::Selectors(name_span)),
+            "accessors" => Ok(MatcherAttr::Accessors(name_span)),
+            other => Err(Error::new(
+                name_span,
+                format!("unknown `matcher` attribute `{}`", other),
+            )),
+        }
+    }
+}
+
+/// Go through a set of `Attribute`s and process them into an iterator of parsed attributes
+fn process_attrs(input: Vec<Attribute>) -> impl Iterator<Item = Result<MatcherAttr>> {
+    ProcessAttrs {
+        attrs: input.into_iter(),
+        part: None,
+    }
+}
+
+struct ProcessAttrs {
+    attrs: std::vec::IntoIter<Attribute>,
+    part: Option<syn::punctuated::IntoIter<MatcherAttr>>,
+}
+
+impl ProcessAttrs {
+    /// Get the next part, or `None` if there aren't any.
+    fn next_part(&mut self) -> Option<MatcherAttr> {
+        match &mut self.part {
+            Some(iter) => iter.next(),
+            None => None,
+        }
+    }
+
+    /// Find the next `matches` attr and load it into `part`
+    fn load_parts(&mut self) -> Result<()> {
+        assert!(self.part.as_mut().and_then(|iter| iter.next()).is_none());
+        loop {
+            let attr = match self.attrs.next() {
+                Some(a) => a,
+                None => return Ok(()),
+            };
+            if !matches_path(&attr.path) {
+                continue;
+            }
+            let match_attrs: Punctuated<_, Token![,]> =
+                attr.parse_args_with(Punctuated::parse_terminated)?;
+            self.part = Some(match_attrs.into_iter());
+            return Ok(());
+        }
+    }
+}
+
+impl Iterator for ProcessAttrs {
+    type Item = Result<MatcherAttr>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(matcher_attr) = self.next_part() {
+            return Some(Ok(matcher_attr));
+        }
+        if let Err(e) = self.load_parts() {
+            return Some(Err(e));
+        }
+        self.next_part().map(Ok)
+    }
+}
+
+/// True if the path is exactly `matches`.
+fn matches_path(p: &Path) -> bool {
+    if p.leading_colon.is_some() {
+        return false;
+    }
+    let segment = match iter_get_one(p.segments.iter()) {
+        Some(s) => s,
+        None => return false,
+    };
+    segment.ident == "matcher" && segment.arguments.is_empty()
+}
+
+fn iter_get_one<T, I: IntoIterator<Item = T>>(iter: I) -> Option<T> {
+    let mut iter = iter.into_iter();
+    let result = iter.next()?;
+    if iter.next().is_some() {
+        return None;
+    }
+    Some(result)
+}
+
+/// The snake case of `input`, as a raw identifier if that's a keyword, or with a trailing `_` if
+/// it's one of the keywords that can't be raw.
+fn snakify(input: &Ident) -> Ident {
+    let new_name = input.unraw().to_string().to_snake_case();
+    match new_name.as_str() {
+        "crate" | "self" | "super" | "_" => Ident::new(&format!("{}_", new_name), input.span()),
+        name if KEYWORDS.contains(&name) => Ident::new_raw(name, input.span()),
+        _ => Ident::new(&new_name, input.span()),
+    }
+}
+
+/// The lowercase keywords, strict and reserved, of every edition.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Names the matchers already use, for their own builder methods, their `Widget` methods and
+/// their fields and helpers.
+const RESERVED_BUILDER_NAMES: &[&str] = &[
+    "new",
+    "default",
+    "default_empty",
+    "keep_alive",
+    "on_missing",
+    "on_enter",
+    "on_exit",
+    "on_change",
+    "transition",
+    "unchecked",
+    "event",
+    "lifecycle",
+    "update",
+    "layout",
+    "paint",
+    "default_",
+    "discriminant_",
+    "added_",
+    "default_added_",
+    "keep_alive_",
+    "missing_",
+    "enter_hooks_",
+    "exit_hooks_",
+    "change_hooks_",
+    "animation_",
+    "env_",
+    "slot_",
+    "hidden_",
+    "enter_",
+    "exit_",
+    "project_",
+    "write_back_",
+    "same_",
+    "paint_data_",
+    "background_event_",
+    "background_lifecycle_",
+    "background_update_",
+];
+
+/// Checks that the names generated for each variant are neither reserved nor generated for
+/// another variant, which would otherwise be confusing errors about duplicate definitions.
+fn check_names(input: &MatcherDerive) -> Result<()> {
+    let mut errors: Option<Error> = None;
+    let mut error = |span: Span, message: String| {
+        let error = Error::new(span, message);
+        match &mut errors {
+            Some(errors) => errors.combine(error),
+            None => errors = Some(error),
+        }
+    };
+    // The types generated next to the enum for all of its variants.
+    let mut matcher_names = vec![
+        input.resolve_matcher_name(),
+        input.resolve_shared_matcher_name(),
+    ];
+    if input.exhaustive {
+        matcher_names = matcher_names
+            .iter()
+            .flat_map(|name| vec![name.clone(), unchecked_name(name)])
+            .collect();
+    }
+    // Each generated name, with where it's defined and the variant it's generated for.
+    let mut names: Vec<((Namespace, String), &MatcherVariant)> = Vec::new();
+    for variant in &input.variants {
+        let builder_name = variant.resolve_builder_name().unraw().to_string();
+        let span = variant
+            .builder_name
+            .as_ref()
+            .map_or_else(|| variant.name.span(), Ident::span);
+        let view_span = variant
+            .view_name
+            .as_ref()
+            .map_or_else(|| variant.name.span(), Ident::span);
+        if RESERVED_BUILDER_NAMES.contains(&builder_name.as_str()) {
+            error(
+                span,
+                format!(
+                    "`{}` is already a method or field of the matcher, set another name with \
+                     `#[matcher(builder_name = ...)]`",
+                    builder_name
+                ),
+            );
+        }
+        let has_default = variant.default_data.is_some() || matches!(variant.fields, Fields::Unit);
+        if has_default && variant.resolve_base_name() == "for" {
+            error(
+                span,
+                String::from(
+                    "`default_for` would shadow `Matchable::default_for`, set another name with \
+                     `#[matcher(builder_name = ...)]`",
+                ),
+            );
+        }
+        let base_name = variant.resolve_base_name();
+        let mut generated = vec![
+            (Namespace::Matcher, builder_name.clone()),
+            (Namespace::Matcher, format!("{}_with", base_name)),
+            (
+                Namespace::Enum,
+                prism_name(&input.variants, variant).to_string(),
+            ),
+        ];
+        if has_default {
+            generated.push((Namespace::Enum, format!("default_{}", base_name)));
+        }
+        if input.selectors {
+            generated.push((Namespace::Enum, selector_name(variant).to_string()));
+        }
+        if input.accessors {
+            for (prefix, suffix) in &[("is_", ""), ("as_", ""), ("as_", "_mut"), ("into_", "")] {
+                generated.push((
+                    Namespace::Enum,
+                    format!("{}{}{}", prefix, base_name, suffix),
+                ));
+            }
+        }
+        if let Fields::Named(_) = variant.fields {
+            let view_name = variant.resolve_view_name();
+            if view_name == input.enum_name || matcher_names.contains(&view_name) {
+                error(
+                    view_span,
+                    format!(
+                        "`{}` is already generated for `{}`, set another name with \
+                         `#[matcher(view_name = ...)]`",
+                        view_name, input.enum_name
+                    ),
+                );
+            }
+            generated.push((Namespace::Module, view_name.to_string()));
+        }
+        // Only the first name shared with each other variant is reported.
+        let mut clashing: Vec<&Ident> = Vec::new();
+        for name in generated {
+            match names.iter().find(|(other, _)| *other == name) {
+                Some((_, other)) if !clashing.contains(&&other.name) => {
+                    clashing.push(&other.name);
+                    let (span, attribute) = match name.0 {
+                        Namespace::Module => (view_span, "view_name"),
+                        _ => (span, "builder_name"),
+                    };
+                    error(
+                        span,
+                        format!(
+                            "`{}` and `{}` both generate `{}`, set another name for one of them \
+                             with `#[matcher({} = ...)]`",
+                            other.name, variant.name, name.1, attribute
+                        ),
+                    );
+                }
+                Some(_) => (),
+                None => names.push((name, variant)),
+            }
+        }
+    }
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(()),
+    }
+}
+
+/// Where a generated name is defined.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Namespace {
+    /// The matchers' methods and fields.
+    Matcher,
+    /// The enum's associated functions and constants.
+    Enum,
+    /// The types next to the enum.
+    Module,
+}
@@ -0,0 +1,139 @@
+use heck::{ShoutySnakeCase, SnakeCase};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::Ident;
+use syn::{ext::IdentExt, parse_quote, Fields};
+
+use crate::matcher::{data_of, fields_of, same_fns, type_of, write_back_field};
+use crate::parse::{MatcherDerive, MatcherVariant};
+
+/// The name of the associated constant holding the prism of `variant`, its builder name in upper
+/// case, with a trailing `_` if that's the name of a variant, which would shadow it.
+pub fn prism_name(variants: &[MatcherVariant], variant: &MatcherVariant) -> Ident {
+    let name = variant.resolve_base_name().to_shouty_snake_case();
+    if variants.iter().any(|other| other.name.unraw() == name) {
+        format_ident!("{}_", name, span = variant.name.span())
+    } else {
+        format_ident!("{}", name, span = variant.name.span())
+    }
+}
+
+/// Generates a `Prism` for each variant, in a module next to the enum, and an associated constant
+/// on the enum for each of them.
+pub fn prisms(input: &MatcherDerive) -> TokenStream {
+    let visibility = &input.visibility;
+    let enum_name = &input.enum_name;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let module = format_ident!("{}_derived_prisms", enum_name.to_string().to_snake_case());
+
+    // Variants that aren't a single field are focused on through an owned value, which is cloned
+    // from the fields and written back to those that changed.
+    let mut data_generics = input.generics.clone();
+    {
+        let predicates = &mut data_generics.make_where_clause().predicates;
+        for param in input.generics.type_params() {
+            let param = &param.ident;
+            predicates.push(parse_quote!(#param: ::druid::Data));
+        }
+    }
+    let (_, _, data_where_clause) = data_generics.split_for_impl();
+
+    let structs = input.variants.iter().map(|variant| {
+        let variant_name = &variant.name;
+        let doc = format!(
+            "Focuses on the data of [`{}::{}`].",
+            enum_name, variant_name
+        );
+        quote! {
+            #[doc = #doc]
+            #[derive(Clone, Copy, Debug)]
+            pub struct #variant_name;
+        }
+    });
+
+    let consts = input.variants.iter().map(|variant| {
+        let variant_name = &variant.name;
+        let const_name = prism_name(&input.variants, variant);
+        let doc = format!("The prism focusing on [`{}::{}`].", enum_name, variant_name);
+        quote! {
+            #[doc = #doc]
+            pub const #const_name: #module::#variant_name = #module::#variant_name;
+        }
+    });
+
+    let impls = input.variants.iter().map(|variant| {
+        let variant_name = &variant.name;
+        let variant_ty = type_of(variant, &input.generics);
+        let enum_ty = quote!(#enum_name #ty_generics);
+        let (with, with_mut, where_clause) = match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => (
+                quote!(#enum_name::#variant_name(p0) => Some(f(p0))),
+                quote!(#enum_name::#variant_name(p0) => Some(f(p0))),
+                where_clause,
+            ),
+            _ => {
+                let (data_pattern, data_values, _) = data_of(enum_name, variant, "", &quote!(d));
+                let write_back = fields_of(variant, "", &quote!(d))
+                    .into_iter()
+                    .zip(same_fns(variant))
+                    .map(|((place, binding), same_fn)| {
+                        write_back_field(&same_fn, &place, &binding)
+                    });
+                (
+                    quote!(#enum_name::#variant_name #data_pattern => Some(f(&#data_values))),
+                    quote! {
+                        #enum_name::#variant_name #data_pattern => {
+                            let mut d = #data_values;
+                            let value = f(&mut d);
+                            #(#write_back)*
+                            Some(value)
+                        }
+                    },
+                    data_where_clause,
+                )
+            }
+        };
+        quote! {
+            impl #impl_generics ::druid_enums::Prism<#enum_ty, #variant_ty> for #module::#variant_name
+                #where_clause
+            {
+                fn with<V, F: FnOnce(&#variant_ty) -> V>(&self, data: &#enum_ty, f: F) -> Option<V> {
+                    #[allow(unreachable_patterns)]
+                    match data {
+                        #with,
+                        _ => None,
+                    }
+                }
+                fn with_mut<V, F: FnOnce(&mut #variant_ty) -> V>(
+                    &self,
+                    data: &mut #enum_ty,
+                    f: F,
+                ) -> Option<V> {
+                    #[allow(unreachable_patterns)]
+                    match data {
+                        #with_mut,
+                        _ => None,
+                    }
+                }
+            }
+        }
+    });
+
+    let module_doc = format!(
+        "The prisms generated for the variants of [`{}`].",
+        enum_name
+    );
+    quote! {
+        #[doc = #module_doc]
+        #[allow(non_camel_case_types)]
+        #visibility mod #module {
+            #(#structs)*
+        }
+
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            #(#consts)*
+        }
+
+        #(#impls)*
+    }
+}
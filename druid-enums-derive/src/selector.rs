@@ -0,0 +1,44 @@
+use heck::ShoutySnakeCase;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::Ident;
+
+use crate::matcher::type_of;
+use crate::parse::{MatcherDerive, MatcherVariant};
+
+/// The name of the associated constant holding the selector that switches to `variant`, after its
+/// builder name.
+pub fn selector_name(variant: &MatcherVariant) -> Ident {
+    format_ident!(
+        "GOTO_{}",
+        variant.resolve_base_name().to_shouty_snake_case(),
+        span = variant.name.span()
+    )
+}
+
+/// Generates a `Selector` for each variant as an associated constant on the enum, carrying the
+/// data the variant's widget sees.
+pub fn selectors(input: &MatcherDerive) -> TokenStream {
+    let enum_name = &input.enum_name;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let consts = input.variants.iter().map(|variant| {
+        let variant_name = &variant.name;
+        let selector_name = selector_name(variant);
+        let payload_ty = type_of(variant, &input.generics);
+        let doc = format!(
+            "Switches the matchers of [`{}`] that receive it to [`{0}::{}`].",
+            enum_name, variant_name
+        );
+        quote! {
+            #[doc = #doc]
+            pub const #selector_name: ::druid::Selector<#payload_ty> = ::druid::Selector::new(
+                concat!(module_path!(), "::", stringify!(#enum_name), "::", stringify!(#selector_name))
+            );
+        }
+    });
+    quote! {
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            #(#consts)*
+        }
+    }
+}
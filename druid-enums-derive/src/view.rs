@@ -0,0 +1,222 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{
+    parse_quote, punctuated::Punctuated, Error, ExprPath, Field, FieldsNamed, GenericParam,
+    Generics, Ident, Lit, Meta, MetaNameValue, NestedMeta, Result, TypeParamBound, Visibility,
+    WhereClause, WherePredicate,
+};
+
+use crate::parse::MatcherVariant;
+
+/// Generates the struct that holds the fields of a variant with named fields.
+///
+/// This struct is what the widget for the variant gets as its data, so it derives `Data`, and
+/// `Lens` when possible. Druid can't derive either for generic structs with bounds, so when the
+/// fields mention any of the enum's generic parameters the lenses are left out and `Data` is
+/// implemented here instead.
+pub fn view_struct(
+    visibility: &Visibility,
+    enum_name: &Ident,
+    generics: &Generics,
+    variant: &MatcherVariant,
+    fields: &FieldsNamed,
+) -> TokenStream {
+    let view_name = variant.resolve_view_name();
+    let view_generics = view_generics(generics, fields);
+    let (impl_generics, ty_generics, where_clause) = view_generics.split_for_impl();
+    let doc = format!(
+        "The fields of [`{}::{}`], as seen by its widget in the matcher.",
+        enum_name, variant.name
+    );
+    let generic = !view_generics.params.is_empty();
+    let derives = if generic {
+        quote!(Clone)
+    } else {
+        quote!(Clone, ::druid::Data, ::druid::Lens)
+    };
+    let field_defs = fields.named.iter().map(|field| {
+        let attrs = field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("doc") || (!generic && attr.path.is_ident("data")));
+        let name = &field.ident;
+        let ty = &field.ty;
+        quote!(#(#attrs)* #visibility #name: #ty)
+    });
+    let data_impl = if generic {
+        let mut data_generics = view_generics.clone();
+        {
+            let predicates = &mut data_generics.make_where_clause().predicates;
+            for param in view_generics.type_params() {
+                let param = &param.ident;
+                predicates.push(parse_quote!(#param: ::druid::Data));
+            }
+        }
+        let (_, _, data_where_clause) = data_generics.split_for_impl();
+        let sames = fields
+            .named
+            .iter()
+            .filter_map(|field| {
+                let name = &field.ident;
+                match same_fn(field) {
+                    Ok(SameFn::Ignore) => None,
+                    Ok(SameFn::Path(path)) => Some(quote!(#path(&self.#name, &other.#name))),
+                    Ok(SameFn::Data) => {
+                        Some(quote!(::druid::Data::same(&self.#name, &other.#name)))
+                    }
+                    Err(error) => Some(error.to_compile_error()),
+                }
+            })
+            .collect::<Vec<_>>();
+        let same = if sames.is_empty() {
+            quote!(true)
+        } else {
+            quote!(#(#sames)&&*)
+        };
+        quote! {
+            impl #impl_generics ::druid::Data for #view_name #ty_generics #data_where_clause {
+                fn same(&self, other: &Self) -> bool {
+                    #same
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+    quote! {
+        #[doc = #doc]
+        #[derive(#derives)]
+        #visibility struct #view_name #impl_generics #where_clause {
+            #(#field_defs,)*
+        }
+
+        #data_impl
+    }
+}
+
+/// How a field of a view struct is compared, from its `#[data(...)]` attribute.
+pub enum SameFn {
+    Data,
+    Ignore,
+    Path(ExprPath),
+}
+
+/// Reads `#[data(ignore)]` and `#[data(same_fn = "path")]` like `#[derive(Data)]` does.
+pub fn same_fn(field: &Field) -> Result<SameFn> {
+    let mut same_fn = SameFn::Data;
+    for attr in field.attrs.iter().filter(|attr| attr.path.is_ident("data")) {
+        let nested = match attr.parse_meta()? {
+            Meta::List(list) => list.nested,
+            meta => return Err(Error::new_spanned(meta, "expected `data(...)`")),
+        };
+        for meta in nested {
+            match meta {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("ignore") => {
+                    same_fn = SameFn::Ignore;
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(lit),
+                    ..
+                })) if path.is_ident("same_fn") => {
+                    same_fn = SameFn::Path(lit.parse()?);
+                }
+                meta => {
+                    return Err(Error::new_spanned(
+                        meta,
+                        "expected `ignore` or `same_fn = \"...\"`",
+                    ))
+                }
+            }
+        }
+    }
+    Ok(same_fn)
+}
+
+/// The generic parameters of the enum that are used by the fields of a variant, with their bounds
+/// and the predicates of the enum's where clause that only mention them.
+///
+/// Lifetimes are never included as `Data` requires `'static` anyway.
+pub fn view_generics(generics: &Generics, fields: &FieldsNamed) -> Generics {
+    let fields = fields.to_token_stream();
+    let mut dropped = Vec::new();
+    let params = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(param) if mentions(&fields, &param.ident) => {
+                let mut param = param.clone();
+                param.attrs.clear();
+                param.eq_token = None;
+                param.default = None;
+                param.bounds = param
+                    .bounds
+                    .into_iter()
+                    .filter(|bound| !matches!(bound, TypeParamBound::Lifetime(_)))
+                    .collect();
+                if param.bounds.is_empty() {
+                    param.colon_token = None;
+                }
+                Some(GenericParam::Type(param))
+            }
+            GenericParam::Const(param) if mentions(&fields, &param.ident) => {
+                let mut param = param.clone();
+                param.eq_token = None;
+                param.default = None;
+                Some(GenericParam::Const(param))
+            }
+            GenericParam::Type(param) => {
+                dropped.push(param.ident.clone());
+                None
+            }
+            GenericParam::Const(param) => {
+                dropped.push(param.ident.clone());
+                None
+            }
+            GenericParam::Lifetime(param) => {
+                dropped.push(param.lifetime.ident.clone());
+                None
+            }
+        })
+        .collect::<Punctuated<_, _>>();
+    let predicates = generics
+        .where_clause
+        .iter()
+        .flat_map(|where_clause| &where_clause.predicates)
+        .filter(|predicate| match predicate {
+            WherePredicate::Type(_) => {
+                let predicate = predicate.to_token_stream();
+                params.iter().any(|param| match param {
+                    GenericParam::Type(param) => mentions(&predicate, &param.ident),
+                    GenericParam::Const(param) => mentions(&predicate, &param.ident),
+                    GenericParam::Lifetime(_) => false,
+                }) && !dropped.iter().any(|ident| mentions(&predicate, ident))
+            }
+            _ => false,
+        })
+        .cloned()
+        .collect::<Punctuated<_, _>>();
+    let where_clause = if predicates.is_empty() {
+        None
+    } else {
+        Some(WhereClause {
+            where_token: Default::default(),
+            predicates,
+        })
+    };
+    Generics {
+        lt_token: Some(Default::default()),
+        params,
+        gt_token: Some(Default::default()),
+        where_clause,
+    }
+}
+
+/// True if `ident` appears anywhere in `tokens`.
+pub fn mentions(tokens: &TokenStream, ident: &Ident) -> bool {
+    tokens.clone().into_iter().any(|token| match token {
+        TokenTree::Ident(other) => &other == ident,
+        TokenTree::Group(group) => mentions(&group.stream(), ident),
+        _ => false,
+    })
+}
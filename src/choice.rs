@@ -0,0 +1,156 @@
+//! Widgets choosing one of the variants of an enum, for enums without fields.
+
+use druid::{
+    theme,
+    widget::{CrossAxisAlignment, Flex, Label, RadioGroup},
+    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, Lens, LifeCycle, LifeCycleCtx, PaintCtx,
+    Point, Rect, RenderContext, Size, UpdateCtx, Widget, WidgetExt, WidgetPod,
+};
+
+use crate::Matchable;
+
+/// The lens from an enum to the index of its variant.
+///
+/// Setting another index switches to [`Matchable::default_for`] it, and does nothing for variants
+/// without a default.
+#[derive(Clone, Copy, Debug)]
+pub struct VariantIndex;
+
+impl<T: Matchable> Lens<T, usize> for VariantIndex {
+    fn with<V, F: FnOnce(&usize) -> V>(&self, data: &T, f: F) -> V {
+        f(&data.variant_index())
+    }
+
+    fn with_mut<V, F: FnOnce(&mut usize) -> V>(&self, data: &mut T, f: F) -> V {
+        let mut index = data.variant_index();
+        let value = f(&mut index);
+        if index != data.variant_index() {
+            if let Some(variant) = T::default_for(index) {
+                *data = variant;
+            }
+        }
+        value
+    }
+}
+
+/// The variants that can be chosen, those with a default, with their labels.
+fn choices<T: Matchable>() -> impl Iterator<Item = (usize, &'static str)> {
+    T::VARIANT_LABELS
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| T::default_for(*index).is_some())
+        .map(|(index, label)| (index, *label))
+}
+
+/// A radio button for each variant, labelled like [`Tabs`](crate::Tabs).
+///
+/// Meant for enums without fields, other variants are only listed if they have a default.
+pub fn radio_group<T: Matchable + Data>() -> impl Widget<T> {
+    RadioGroup::new(choices::<T>().map(|(index, label)| (label, index))).lens::<T, _>(VariantIndex)
+}
+
+/// A button showing the label of the current variant, which lists the others below it when
+/// clicked.
+///
+/// Druid has no popups, so the list takes up space in the layout while it's open. Meant for enums
+/// without fields, other variants are only listed if they have a default.
+pub fn dropdown<T: Matchable + Data>() -> impl Widget<T> {
+    Dropdown::new(choices::<T>().collect(), T::VARIANT_LABELS).lens::<T, _>(VariantIndex)
+}
+
+struct Dropdown {
+    header: WidgetPod<usize, Box<dyn Widget<usize>>>,
+    list: WidgetPod<usize, Box<dyn Widget<usize>>>,
+    open: bool,
+}
+
+impl Dropdown {
+    fn new(choices: Vec<(usize, &'static str)>, labels: &'static [&'static str]) -> Self {
+        let header = Label::new(move |index: &usize, _: &Env| format!("{} ▾", labels[*index]))
+            .padding((8.0, 4.0))
+            .border(theme::BORDER_DARK, 1.0);
+        let mut list = Flex::column().cross_axis_alignment(CrossAxisAlignment::Start);
+        for (index, label) in choices {
+            list.add_child(
+                Label::new(label)
+                    .padding((8.0, 4.0))
+                    .on_click(move |_, data: &mut usize, _| *data = index),
+            );
+        }
+        Dropdown {
+            header: WidgetPod::new(header.boxed()),
+            list: WidgetPod::new(list.boxed()),
+            open: false,
+        }
+    }
+}
+
+impl Widget<usize> for Dropdown {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut usize, env: &Env) {
+        if let Event::MouseDown(mouse) = event {
+            if self.header.layout_rect().contains(mouse.pos) {
+                self.open = !self.open;
+                ctx.request_layout();
+                ctx.set_handled();
+                return;
+            }
+        }
+        if self.open {
+            let old = *data;
+            self.list.event(ctx, event, data, env);
+            if *data != old {
+                self.open = false;
+                ctx.request_layout();
+            }
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &usize, env: &Env) {
+        self.header.lifecycle(ctx, event, data, env);
+        self.list.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _: &usize, data: &usize, env: &Env) {
+        self.header.update(ctx, data, env);
+        self.list.update(ctx, data, env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &usize,
+        env: &Env,
+    ) -> Size {
+        let loose = bc.loosen();
+        let header_size = self.header.layout(ctx, &loose, data, env);
+        self.header
+            .set_layout_rect(ctx, data, env, header_size.to_rect());
+        let list_size = self.list.layout(ctx, &loose, data, env);
+        let list_origin = Point::new(0.0, header_size.height);
+        self.list.set_layout_rect(
+            ctx,
+            data,
+            env,
+            Rect::from_origin_size(list_origin, list_size),
+        );
+        let size = if self.open {
+            Size::new(
+                header_size.width.max(list_size.width),
+                header_size.height + list_size.height,
+            )
+        } else {
+            header_size
+        };
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &usize, env: &Env) {
+        self.header.paint(ctx, data, env);
+        if self.open {
+            let list_rect = self.list.layout_rect();
+            ctx.fill(list_rect, &env.get(theme::BACKGROUND_LIGHT));
+            self.list.paint(ctx, data, env);
+        }
+    }
+}
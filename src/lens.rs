@@ -0,0 +1,69 @@
+//! Lenses for handing a matcher its data.
+
+use druid::{Data, Lens};
+use std::marker::PhantomData;
+
+/// A lens that applies two lenses to the same data, almost like a parallel version of
+/// `druid::lens::Then`.
+///
+/// This gets both the shared data and the enum out of a larger state for a shared matcher.
+/// Changes are only written back to the parts that aren't `same` as before.
+pub struct ChainLens<In, Lens1, Lens2> {
+    lens1: Lens1,
+    lens2: Lens2,
+    // The following is a workaround for otherwise getting E0207.
+    phantom_in: PhantomData<In>,
+}
+
+impl<In, Lens1, Lens2> ChainLens<In, Lens1, Lens2> {
+    pub fn new(lens1: Lens1, lens2: Lens2) -> ChainLens<In, Lens1, Lens2> {
+        ChainLens {
+            lens1,
+            lens2,
+            phantom_in: PhantomData,
+        }
+    }
+}
+
+impl<In, Lens1, Lens2, Out1, Out2> Lens<In, (Out1, Out2)> for ChainLens<In, Lens1, Lens2>
+where
+    Lens1: Lens<In, Out1>,
+    Lens2: Lens<In, Out2>,
+    Out1: Data,
+    Out2: Data,
+{
+    fn with<R, F: FnOnce(&(Out1, Out2)) -> R>(&self, data: &In, f: F) -> R {
+        let out1 = self.lens1.with(data, Out1::clone);
+        let out2 = self.lens2.with(data, Out2::clone);
+        f(&(out1, out2))
+    }
+
+    fn with_mut<R, F: FnOnce(&mut (Out1, Out2)) -> R>(&self, data: &mut In, f: F) -> R {
+        let out1 = self.lens1.with(data, Out1::clone);
+        let out2 = self.lens2.with(data, Out2::clone);
+        let mut out = (out1, out2);
+        let result = f(&mut out);
+        let (new_out1, new_out2) = out;
+        self.lens1.with_mut(data, |out1| {
+            if !out1.same(&new_out1) {
+                *out1 = new_out1;
+            }
+        });
+        self.lens2.with_mut(data, |out2| {
+            if !out2.same(&new_out2) {
+                *out2 = new_out2;
+            }
+        });
+        result
+    }
+}
+
+/// Provides [`zip`](LensZipExt::zip) for all lenses.
+pub trait LensZipExt<In, Out1>: Lens<In, Out1> + Sized {
+    /// Applies this lens and `other` to the same data, see [`ChainLens`].
+    fn zip<Out2, Other: Lens<In, Out2>>(self, other: Other) -> ChainLens<In, Self, Other> {
+        ChainLens::new(self, other)
+    }
+}
+
+impl<In, Out1, L: Lens<In, Out1>> LensZipExt<In, Out1> for L {}
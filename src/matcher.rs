@@ -0,0 +1,138 @@
+//! Traits implemented by `#[derive(Matcher)]`, for code that works with any matched enum.
+
+use druid::{widget::Label, Color, Data, Env, Widget, WidgetExt, WidgetId};
+
+/// An enum with a matcher, the `Widget<Enum>` returned by `Enum::matcher()`.
+pub trait Matcher: Data {
+    type Matcher: Widget<Self>;
+
+    /// A matcher without any variant widgets set.
+    fn matcher() -> Self::Matcher;
+}
+
+/// An enum with a shared matcher, the `Widget<(Shared, Enum)>` returned by
+/// `Enum::shared_matcher()`.
+pub trait SharedMatcher<Shared: Data>: Data {
+    type SharedMatcher: Widget<(Shared, Self)>;
+
+    /// A shared matcher without any variant widgets set.
+    fn shared_matcher() -> Self::SharedMatcher;
+}
+
+/// An enum that knows its variants, implemented by `#[derive(Matcher)]`.
+pub trait Matchable {
+    /// The names of the variants, in declaration order.
+    const VARIANT_NAMES: &'static [&'static str];
+    /// The text shown for each variant in widgets like [`Tabs`](crate::Tabs), the name of the
+    /// variant unless it has a `#[matcher(label = "...")]`.
+    const VARIANT_LABELS: &'static [&'static str];
+    const VARIANT_COUNT: usize;
+
+    /// The position of this value's variant in the declaration of the enum.
+    fn variant_index(&self) -> usize;
+
+    /// The name of this value's variant.
+    fn variant_name(&self) -> &'static str {
+        Self::VARIANT_NAMES[self.variant_index()]
+    }
+
+    /// The variant at `index` with its default data, if it has any. Unit variants always do,
+    /// others need a `#[matcher(default)]` or `#[matcher(default_data = ...)]`.
+    fn default_for(index: usize) -> Option<Self>
+    where
+        Self: Sized;
+}
+
+/// Marks a variant of an exhaustive matcher that has a widget.
+pub enum Set {}
+
+/// Marks a variant of an exhaustive matcher that doesn't have a widget yet.
+pub enum Unset {}
+
+/// What a matcher does about a variant with neither a widget of its own nor a default to fall
+/// back to, see `on_missing` on the generated matchers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Missing {
+    /// Log a warning when the matcher is added, and show nothing for the variant.
+    Warn,
+    /// Panic when the matcher is added.
+    Panic,
+    /// Show a widget naming the enum and the variant instead.
+    Placeholder,
+}
+
+impl Default for Missing {
+    /// `Panic` in debug builds, `Warn` otherwise.
+    fn default() -> Self {
+        if cfg!(debug_assertions) {
+            Missing::Panic
+        } else {
+            Missing::Warn
+        }
+    }
+}
+
+/// Reports a variant that has neither a widget of its own nor a default to fall back to,
+/// returning whether the matcher should show a [`placeholder`] for it.
+#[doc(hidden)]
+pub fn missing(policy: Missing, matcher: &str, builder: &str, id: WidgetId) -> bool {
+    match policy {
+        Missing::Warn => {
+            log::warn!(
+                "{}::{} variant of {:?} has not been set.",
+                matcher,
+                builder,
+                id
+            );
+            false
+        }
+        Missing::Panic => panic!(
+            "{}::{} variant of {:?} has not been set.",
+            matcher, builder, id
+        ),
+        Missing::Placeholder => true,
+    }
+}
+
+/// The widget shown for variants without a widget with [`Missing::Placeholder`].
+#[doc(hidden)]
+pub fn placeholder<T: Data>(
+    enum_name: &'static str,
+    variant_name: fn(&T) -> &'static str,
+) -> impl Widget<T> {
+    Label::new(move |data: &T, _: &Env| {
+        format!("{}::{} has no widget", enum_name, variant_name(data))
+    })
+    .with_text_color(Color::rgb8(0xff, 0x40, 0x40))
+    .center()
+    .border(Color::rgb8(0xff, 0x40, 0x40), 1.0)
+}
+
+/// Sets `cache` to `data` unless it's already the same, returning whether it changed.
+#[doc(hidden)]
+pub fn refresh<T: Data>(cache: &mut T, data: &T) -> bool {
+    refresh_by(cache, data, T::same)
+}
+
+/// Sets `cache` to `data` unless `same` says it already is, returning whether it changed.
+#[doc(hidden)]
+pub fn refresh_by<T: Clone>(cache: &mut T, data: &T, same: impl Fn(&T, &T) -> bool) -> bool {
+    if same(cache, data) {
+        false
+    } else {
+        *cache = data.clone();
+        true
+    }
+}
+
+/// Sets `data` to `cache` unless it's already the same, returning whether it changed.
+#[doc(hidden)]
+pub fn write_back<T: Data>(cache: &T, data: &mut T) -> bool {
+    refresh(data, cache)
+}
+
+/// Sets `data` to `cache` unless `same` says it already is, returning whether it changed.
+#[doc(hidden)]
+pub fn write_back_by<T: Clone>(cache: &T, data: &mut T, same: impl Fn(&T, &T) -> bool) -> bool {
+    refresh_by(data, cache, same)
+}
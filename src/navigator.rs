@@ -0,0 +1,259 @@
+//! A widget keeping a history of the variants of an enum, for wizard-like flows.
+
+use druid::{
+    lens, BoxConstraints, Command, Data, Env, Event, EventCtx, Key, LayoutCtx, Lens, LifeCycle,
+    LifeCycleCtx, PaintCtx, Selector, Size, UpdateCtx, Widget, WidgetPod,
+};
+use std::{any::Any, collections::VecDeque, mem};
+
+/// Goes back to the previous value in the history of the `Navigator` receiving it.
+pub const BACK: Selector = Selector::new("druid-enums.navigator.back");
+
+/// Goes forward to the value the `Navigator` receiving it last went back from.
+pub const FORWARD: Selector = Selector::new("druid-enums.navigator.forward");
+
+/// Replaces the value of the `Navigator` receiving it without adding to its history, see
+/// [`replace`].
+///
+/// Navigators ignore payloads of another type than their enum.
+pub const REPLACE: Selector<Box<dyn Any>> = Selector::new("druid-enums.navigator.replace");
+
+/// Whether the closest `Navigator` has a value to go back to.
+pub const CAN_GO_BACK: Key<bool> = Key::new("druid-enums.navigator.can-go-back");
+
+/// Whether the closest `Navigator` has a value to go forward to.
+pub const CAN_GO_FORWARD: Key<bool> = Key::new("druid-enums.navigator.can-go-forward");
+
+/// The command replacing the value of a `Navigator` over `T` with `value`.
+pub fn replace<T: Any>(value: T) -> Command {
+    REPLACE.with(Box::new(value))
+}
+
+/// The values a `Navigator` went through, around the current one.
+#[derive(Clone, Debug)]
+pub struct History<T> {
+    back: VecDeque<T>,
+    forward: Vec<T>,
+    max_depth: Option<usize>,
+    // The value the history last went to, until `record` sees it.
+    arrived: Option<T>,
+}
+
+impl<T> History<T> {
+    pub fn new() -> Self {
+        History {
+            back: VecDeque::new(),
+            forward: Vec::new(),
+            max_depth: None,
+            arrived: None,
+        }
+    }
+
+    /// Keeps at most `max_depth` values to go back to, forgetting the oldest ones.
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = Some(max_depth);
+        self.trim();
+    }
+
+    /// Records that `previous` was left for a new value, which clears the values to go forward
+    /// to.
+    pub fn push(&mut self, previous: T) {
+        self.back.push_back(previous);
+        self.forward.clear();
+        self.trim();
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        !self.back.is_empty()
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward.is_empty()
+    }
+
+    fn trim(&mut self) {
+        if let Some(max_depth) = self.max_depth {
+            while self.back.len() > max_depth {
+                self.back.pop_front();
+            }
+        }
+    }
+}
+
+impl<T: Data> History<T> {
+    /// Swaps `current` with the previous value, returning whether there was one.
+    pub fn back(&mut self, current: &mut T) -> bool {
+        match self.back.pop_back() {
+            Some(previous) => {
+                self.forward.push(mem::replace(current, previous));
+                self.arrived = Some(current.clone());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Swaps `current` with the value it was last gone back from, returning whether there was
+    /// one.
+    pub fn forward(&mut self, current: &mut T) -> bool {
+        match self.forward.pop() {
+            Some(next) => {
+                self.back.push_back(mem::replace(current, next));
+                self.arrived = Some(current.clone());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sets `current` to `value` without recording it.
+    pub fn replace(&mut self, current: &mut T, value: T) {
+        *current = value;
+        self.arrived = Some(current.clone());
+    }
+
+    /// Records a switch from `old` to another variant in `new`, returning whether there was one.
+    ///
+    /// Switching to the value the history last went to isn't recorded, as it's already there.
+    pub fn record(&mut self, old: &T, new: &T) -> bool {
+        if let Some(arrived) = self.arrived.take() {
+            if arrived.same(new) {
+                return false;
+            }
+        }
+        if mem::discriminant(old) == mem::discriminant(new) {
+            return false;
+        }
+        self.push(old.clone());
+        true
+    }
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        History::new()
+    }
+}
+
+/// A widget that records each change of variant of the enum its child shows, typically a
+/// matcher, and goes back and forth through them on [`BACK`] and [`FORWARD`].
+///
+/// Its child gets [`CAN_GO_BACK`] and [`CAN_GO_FORWARD`] in its `Env`. Widgets reading them pick
+/// up changes in their next `update` or `paint`, and the navigator requests a paint when they
+/// change.
+pub struct Navigator<T, E, L, W> {
+    child: WidgetPod<T, W>,
+    lens: L,
+    history: History<E>,
+}
+
+impl<E: Data, W: Widget<E>> Navigator<E, E, lens::Id, W> {
+    /// A navigator whose child sees the enum.
+    pub fn new(child: W) -> Self {
+        Navigator::with_lens(child, lens::Id)
+    }
+}
+
+impl<S: Data, E: Data, W: Widget<(S, E)>> Navigator<(S, E), E, Second, W> {
+    /// A navigator whose child sees the enum with shared data, like a shared matcher.
+    ///
+    /// Only the enum is recorded, going back keeps the shared data as it is.
+    pub fn shared(child: W) -> Self {
+        Navigator::with_lens(child, Second)
+    }
+}
+
+impl<T: Data, E: Data, L: Lens<T, E>, W: Widget<T>> Navigator<T, E, L, W> {
+    /// A navigator over the enum `lens` focuses on in the data of `child`.
+    pub fn with_lens(child: W, lens: L) -> Self {
+        Navigator {
+            child: WidgetPod::new(child),
+            lens,
+            history: History::new(),
+        }
+    }
+
+    /// Keeps at most `max_depth` values to go back to.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.history.set_max_depth(max_depth);
+        self
+    }
+
+    fn child_env(&self, env: &Env) -> Env {
+        env.clone()
+            .adding(CAN_GO_BACK, self.history.can_go_back())
+            .adding(CAN_GO_FORWARD, self.history.can_go_forward())
+    }
+}
+
+impl<T: Data, E: Data, L: Lens<T, E>, W: Widget<T>> Widget<T> for Navigator<T, E, L, W> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let Event::Command(command) = event {
+            let history = &mut self.history;
+            let navigated = if command.is(BACK) {
+                self.lens.with_mut(data, |current| history.back(current))
+            } else if command.is(FORWARD) {
+                self.lens.with_mut(data, |current| history.forward(current))
+            } else if let Some(value) = command
+                .get(REPLACE)
+                .and_then(|value| value.downcast_ref::<E>())
+            {
+                self.lens
+                    .with_mut(data, |current| history.replace(current, value.clone()));
+                true
+            } else {
+                false
+            };
+            if navigated {
+                ctx.request_paint();
+                ctx.set_handled();
+                return;
+            }
+        }
+        let env = self.child_env(env);
+        self.child.event(ctx, event, data, &env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        let env = self.child_env(env);
+        self.child.lifecycle(ctx, event, data, &env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        let (lens, history) = (&self.lens, &mut self.history);
+        let recorded = lens.with(old_data, |old| {
+            lens.with(data, |new| history.record(old, new))
+        });
+        if recorded {
+            ctx.request_paint();
+        }
+        let env = self.child_env(env);
+        self.child.update(ctx, data, &env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let env = self.child_env(env);
+        let size = self.child.layout(ctx, bc, data, &env);
+        self.child.set_layout_rect(ctx, data, &env, size.to_rect());
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let env = self.child_env(env);
+        self.child.paint(ctx, data, &env);
+    }
+}
+
+/// The lens from `(Shared, Enum)` to the enum.
+#[derive(Clone, Copy, Debug)]
+pub struct Second;
+
+impl<S, E> Lens<(S, E), E> for Second {
+    fn with<V, F: FnOnce(&E) -> V>(&self, data: &(S, E), f: F) -> V {
+        f(&data.1)
+    }
+
+    fn with_mut<V, F: FnOnce(&mut E) -> V>(&self, data: &mut (S, E), f: F) -> V {
+        f(&mut data.1)
+    }
+}
@@ -0,0 +1,199 @@
+//! Lenses into a single variant of an enum, which may not be there.
+
+use druid::{
+    BoxConstraints, Data, Env, Event, EventCtx, InternalLifeCycle, LayoutCtx, Lens, LifeCycle,
+    LifeCycleCtx, PaintCtx, Size, UpdateCtx, Widget, WidgetPod,
+};
+use std::marker::PhantomData;
+
+/// Like a `druid::Lens`, but the part it focuses on may be missing from the data.
+///
+/// `#[derive(Matcher)]` generates one for each variant of an enum, available as an associated
+/// constant named after the variant, e.g. `AppState::LOGIN`.
+pub trait Prism<T: ?Sized, U: ?Sized> {
+    /// Calls `f` with the part of `data` in focus, if it's there.
+    fn with<V, F: FnOnce(&U) -> V>(&self, data: &T, f: F) -> Option<V>;
+
+    /// Calls `f` with the part of `data` in focus, if it's there, writing back any changes.
+    fn with_mut<V, F: FnOnce(&mut U) -> V>(&self, data: &mut T, f: F) -> Option<V>;
+}
+
+/// Composes prisms with lenses.
+pub trait PrismExt<T: ?Sized, U: ?Sized>: Prism<T, U> + Sized {
+    /// Focuses further into the part in focus with a lens.
+    fn then<W: ?Sized, L: Lens<U, W>>(self, lens: L) -> Then<Self, L, U> {
+        Then {
+            prism: self,
+            lens,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Focuses on the part in focus of the data `lens` focuses on.
+    fn after<S: ?Sized, L: Lens<S, T>>(self, lens: L) -> After<L, Self, T> {
+        After {
+            lens,
+            prism: self,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, P: Prism<T, U>> PrismExt<T, U> for P {}
+
+/// A prism followed by a lens, see [`PrismExt::then`].
+pub struct Then<P, L, U: ?Sized> {
+    prism: P,
+    lens: L,
+    // The following is a workaround for otherwise getting E0207.
+    phantom: PhantomData<U>,
+}
+
+impl<T, U, W, P, L> Prism<T, W> for Then<P, L, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+    W: ?Sized,
+    P: Prism<T, U>,
+    L: Lens<U, W>,
+{
+    fn with<V, F: FnOnce(&W) -> V>(&self, data: &T, f: F) -> Option<V> {
+        let lens = &self.lens;
+        self.prism.with(data, |data| lens.with(data, f))
+    }
+
+    fn with_mut<V, F: FnOnce(&mut W) -> V>(&self, data: &mut T, f: F) -> Option<V> {
+        let lens = &self.lens;
+        self.prism.with_mut(data, |data| lens.with_mut(data, f))
+    }
+}
+
+/// A lens followed by a prism, see [`PrismExt::after`].
+pub struct After<L, P, T: ?Sized> {
+    lens: L,
+    prism: P,
+    // The following is a workaround for otherwise getting E0207.
+    phantom: PhantomData<T>,
+}
+
+impl<S, T, U, L, P> Prism<S, U> for After<L, P, T>
+where
+    S: ?Sized,
+    T: ?Sized,
+    U: ?Sized,
+    L: Lens<S, T>,
+    P: Prism<T, U>,
+{
+    fn with<V, F: FnOnce(&U) -> V>(&self, data: &S, f: F) -> Option<V> {
+        let prism = &self.prism;
+        self.lens.with(data, |data| prism.with(data, f))
+    }
+
+    fn with_mut<V, F: FnOnce(&mut U) -> V>(&self, data: &mut S, f: F) -> Option<V> {
+        let prism = &self.prism;
+        self.lens.with_mut(data, |data| prism.with_mut(data, f))
+    }
+}
+
+/// A widget that shows `inner` with the part of its data a prism focuses on, and nothing when
+/// that part isn't there.
+pub struct PrismWrap<U, P, W> {
+    inner: WidgetPod<U, W>,
+    prism: P,
+    // Whether `inner` has received `WidgetAdded`, which needs its data to be there.
+    added: bool,
+}
+
+impl<U, P, W: Widget<U>> PrismWrap<U, P, W> {
+    pub fn new(inner: W, prism: P) -> Self {
+        PrismWrap {
+            inner: WidgetPod::new(inner),
+            prism,
+            added: false,
+        }
+    }
+}
+
+impl<T, U, P, W> Widget<T> for PrismWrap<U, P, W>
+where
+    T: Data,
+    U: Data,
+    P: Prism<T, U>,
+    W: Widget<U>,
+{
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if self.added {
+            let inner = &mut self.inner;
+            self.prism
+                .with_mut(data, |data| inner.event(ctx, event, data, env));
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if !self.added {
+            match event {
+                LifeCycle::WidgetAdded
+                | LifeCycle::Internal(InternalLifeCycle::RouteWidgetAdded) => {
+                    self.added = self.prism.with(data, |_| ()).is_some();
+                }
+                _ => (),
+            }
+            if !self.added {
+                return;
+            }
+        }
+        let inner = &mut self.inner;
+        self.prism
+            .with(data, |data| inner.lifecycle(ctx, event, data, env));
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        let was_there = self.prism.with(old_data, |_| ()).is_some();
+        let inner = &mut self.inner;
+        let added = self.added;
+        let is_there = self
+            .prism
+            .with(data, |data| {
+                if added {
+                    inner.update(ctx, data, env);
+                }
+            })
+            .is_some();
+        if was_there != is_there {
+            // Also gets `inner` its `WidgetAdded` the first time the data is there.
+            ctx.children_changed();
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        if !self.added {
+            return bc.min();
+        }
+        let inner = &mut self.inner;
+        self.prism
+            .with(data, |data| {
+                let size = inner.layout(ctx, bc, data, env);
+                inner.set_layout_rect(ctx, data, env, size.to_rect());
+                size
+            })
+            .unwrap_or_else(|| bc.min())
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        if self.added {
+            let inner = &mut self.inner;
+            self.prism.with(data, |data| inner.paint(ctx, data, env));
+        }
+    }
+}
+
+/// Provides [`prism`](PrismWidgetExt::prism) for all widgets.
+pub trait PrismWidgetExt<U: Data>: Widget<U> + Sized + 'static {
+    /// Shows this widget with the part of the data `prism` focuses on, and nothing when that
+    /// part isn't there.
+    fn prism<T, P: Prism<T, U>>(self, prism: P) -> PrismWrap<U, P, Self> {
+        PrismWrap::new(self, prism)
+    }
+}
+
+impl<U: Data, W: Widget<U> + 'static> PrismWidgetExt<U> for W {}
@@ -0,0 +1,150 @@
+//! A tab bar switching between the variants of an enum, above the widget showing it.
+
+use druid::{
+    theme,
+    widget::{Label, Painter},
+    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    Point, Rect, RenderContext, Size, UpdateCtx, Widget, WidgetExt, WidgetPod,
+};
+
+use crate::Matchable;
+
+/// A widget with a tab for each variant of an enum above `body`, typically its matcher.
+///
+/// The tab of the current variant is highlighted. Clicking another tab switches to the data that
+/// variant had when it was last left, or else to [`Matchable::default_for`] it, and does nothing
+/// for variants without a default.
+pub struct Tabs<T> {
+    tabs: Vec<WidgetPod<T, Box<dyn Widget<T>>>>,
+    body: WidgetPod<T, Box<dyn Widget<T>>>,
+    // The data of each variant when it was last left.
+    last: Vec<Option<T>>,
+    // The tab the mouse was pressed on.
+    pressed: Option<usize>,
+}
+
+impl<T: Matchable + Data> Tabs<T> {
+    pub fn new(body: impl Widget<T> + 'static) -> Self {
+        let tabs = T::VARIANT_LABELS
+            .iter()
+            .enumerate()
+            .map(|(index, label)| WidgetPod::new(tab(index, label).boxed()))
+            .collect();
+        Tabs {
+            tabs,
+            body: WidgetPod::new(body.boxed()),
+            last: vec![None; T::VARIANT_COUNT],
+            pressed: None,
+        }
+    }
+
+    fn tab_at(&self, pos: Point) -> Option<usize> {
+        self.tabs
+            .iter()
+            .position(|tab| tab.layout_rect().contains(pos))
+    }
+
+    fn select(&self, index: usize, data: &mut T) {
+        if data.variant_index() == index {
+            return;
+        }
+        if let Some(value) = self.last[index].clone().or_else(|| T::default_for(index)) {
+            *data = value;
+        }
+    }
+}
+
+fn tab<T: Matchable + Data>(index: usize, label: &'static str) -> impl Widget<T> {
+    Label::new(label)
+        .padding((12.0, 6.0))
+        .background(Painter::new(move |ctx, data: &T, env| {
+            let rect = ctx.size().to_rect();
+            if data.variant_index() == index {
+                ctx.fill(rect, &env.get(theme::PRIMARY_DARK));
+            } else if ctx.is_hot() {
+                ctx.fill(rect, &env.get(theme::BUTTON_LIGHT));
+            }
+        }))
+}
+
+impl<T: Matchable + Data> Widget<T> for Tabs<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        for tab in &mut self.tabs {
+            tab.event(ctx, event, data, env);
+        }
+        match event {
+            Event::MouseDown(mouse) if mouse.button.is_left() => {
+                self.pressed = self.tab_at(mouse.pos);
+                if self.pressed.is_some() {
+                    ctx.set_active(true);
+                    ctx.set_handled();
+                    return;
+                }
+            }
+            Event::MouseUp(mouse) if ctx.is_active() && mouse.button.is_left() => {
+                ctx.set_active(false);
+                if let Some(index) = self.pressed.take() {
+                    if self.tab_at(mouse.pos) == Some(index) {
+                        self.select(index, data);
+                    }
+                    ctx.set_handled();
+                    return;
+                }
+            }
+            // The tabs highlight on hover.
+            Event::MouseMove(_) => ctx.request_paint(),
+            _ => (),
+        }
+        self.body.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        for tab in &mut self.tabs {
+            tab.lifecycle(ctx, event, data, env);
+        }
+        self.body.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        let old_index = old_data.variant_index();
+        if old_index != data.variant_index() {
+            self.last[old_index] = Some(old_data.clone());
+        }
+        for tab in &mut self.tabs {
+            tab.update(ctx, data, env);
+        }
+        self.body.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let tab_bc = BoxConstraints::new(Size::ZERO, Size::new(f64::INFINITY, bc.max().height));
+        let mut x = 0.0;
+        let mut bar_height: f64 = 0.0;
+        for tab in &mut self.tabs {
+            let size = tab.layout(ctx, &tab_bc, data, env);
+            tab.set_layout_rect(ctx, data, env, Rect::from_origin_size((x, 0.0), size));
+            x += size.width;
+            bar_height = bar_height.max(size.height);
+        }
+
+        let body_bc = bc.shrink((0.0, bar_height));
+        let body_size = self.body.layout(ctx, &body_bc, data, env);
+        let body_rect = Rect::from_origin_size((0.0, bar_height), body_size);
+        self.body.set_layout_rect(ctx, data, env, body_rect);
+
+        bc.constrain(Size::new(
+            x.max(body_size.width),
+            bar_height + body_size.height,
+        ))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        for tab in &mut self.tabs {
+            tab.paint(ctx, data, env);
+        }
+        let bar_height = self.body.layout_rect().y0;
+        let line = Rect::new(0.0, bar_height - 1.0, ctx.size().width, bar_height);
+        ctx.fill(line, &env.get(theme::BORDER_DARK));
+        self.body.paint(ctx, data, env);
+    }
+}
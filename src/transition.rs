@@ -0,0 +1,233 @@
+//! Animations played by a matcher when it switches from one variant to another.
+
+use druid::{
+    kurbo::{BezPath, Shape},
+    theme, Affine, Env, PaintCtx, Rect, RenderContext, Size,
+};
+use std::{fmt, rc::Rc, time::Duration};
+
+/// One of the two widgets painted during a transition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    /// The widget of the variant that was left.
+    Outgoing,
+    /// The widget of the variant that is now shown.
+    Incoming,
+}
+
+/// Paints a custom transition, see [`Transition::custom`].
+pub type PaintTransition = dyn Fn(&mut PaintCtx, &Env, f64, &mut dyn FnMut(&mut PaintCtx, Side));
+
+/// How a matcher animates from the widget of one variant to the next.
+///
+/// While a transition runs, the outgoing widget is painted with the data its variant had when it
+/// was left, but only the incoming widget receives events.
+#[derive(Clone, Default)]
+pub enum Transition {
+    /// Switch without animating.
+    #[default]
+    None,
+    /// Cross-fade from the outgoing widget to the incoming one.
+    ///
+    /// piet can't paint a widget with opacity, so both are painted at once interleaved in thin
+    /// stripes, see [`cross_fade_mask`].
+    CrossFade(Duration),
+    /// Slide the incoming widget in from the right, pushing the outgoing one out to the left.
+    SlideLeft(Duration),
+    /// Slide the incoming widget in from the left, pushing the outgoing one out to the right.
+    SlideRight(Duration),
+    /// Paint the transition with a closure.
+    Custom(Duration, Rc<PaintTransition>),
+}
+
+impl Transition {
+    /// A transition painted by `paint`.
+    ///
+    /// `paint` is called with the progress of the transition, going from `0.0` to `1.0`, and a
+    /// function painting either widget into the given context.
+    pub fn custom(
+        duration: Duration,
+        paint: impl Fn(&mut PaintCtx, &Env, f64, &mut dyn FnMut(&mut PaintCtx, Side)) + 'static,
+    ) -> Self {
+        Transition::Custom(duration, Rc::new(paint))
+    }
+
+    /// How long the transition runs, or `None` if it doesn't animate at all.
+    pub fn duration(&self) -> Option<Duration> {
+        let duration = match self {
+            Transition::None => return None,
+            Transition::CrossFade(duration)
+            | Transition::SlideLeft(duration)
+            | Transition::SlideRight(duration)
+            | Transition::Custom(duration, _) => *duration,
+        };
+        if duration == Duration::from_secs(0) {
+            None
+        } else {
+            Some(duration)
+        }
+    }
+
+    /// The progress after `elapsed`, from `0.0` to `1.0`.
+    pub fn progress(&self, elapsed: Duration) -> f64 {
+        match self.duration() {
+            Some(duration) => (elapsed.as_secs_f64() / duration.as_secs_f64()).min(1.0),
+            None => 1.0,
+        }
+    }
+
+    /// Paints the transition at `progress`, using `paint` to paint either widget.
+    pub fn paint(
+        &self,
+        ctx: &mut PaintCtx,
+        env: &Env,
+        progress: f64,
+        paint: &mut dyn FnMut(&mut PaintCtx, Side),
+    ) {
+        let size = ctx.size();
+        match self {
+            Transition::None => paint(ctx, Side::Incoming),
+            Transition::CrossFade(_) => {
+                paint(ctx, Side::Outgoing);
+                let mask = cross_fade_mask(size, progress);
+                ctx.with_save(|ctx| {
+                    ctx.clip(&mask);
+                    // The outgoing widget shouldn't show through the incoming one.
+                    ctx.fill(&mask, &env.get(theme::WINDOW_BACKGROUND_COLOR));
+                    paint(ctx, Side::Incoming);
+                });
+            }
+            Transition::SlideLeft(_) | Transition::SlideRight(_) => {
+                let direction = match self {
+                    Transition::SlideLeft(_) => 1.0,
+                    _ => -1.0,
+                };
+                let offset = size.width * progress * direction;
+                ctx.with_save(|ctx| {
+                    ctx.clip(size.to_rect());
+                    ctx.with_save(|ctx| {
+                        ctx.transform(Affine::translate((-offset, 0.0)));
+                        paint(ctx, Side::Outgoing);
+                    });
+                    ctx.with_save(|ctx| {
+                        ctx.transform(Affine::translate((size.width * direction - offset, 0.0)));
+                        paint(ctx, Side::Incoming);
+                    });
+                });
+            }
+            Transition::Custom(_, custom) => custom(ctx, env, progress, paint),
+        }
+    }
+}
+
+impl fmt::Debug for Transition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Transition::None => f.write_str("None"),
+            Transition::CrossFade(duration) => f.debug_tuple("CrossFade").field(duration).finish(),
+            Transition::SlideLeft(duration) => f.debug_tuple("SlideLeft").field(duration).finish(),
+            Transition::SlideRight(duration) => {
+                f.debug_tuple("SlideRight").field(duration).finish()
+            }
+            Transition::Custom(duration, _) => f.debug_tuple("Custom").field(duration).finish(),
+        }
+    }
+}
+
+/// The height of the stripes a [`Transition::CrossFade`] interleaves both widgets in.
+pub const CROSS_FADE_STRIPE: f64 = 4.0;
+
+/// The part of a widget of `size` showing the incoming widget during a
+/// [`Transition::CrossFade`] at `progress`.
+///
+/// That's the top `progress` of each stripe of [`CROSS_FADE_STRIPE`] height, while the outgoing
+/// widget shows through the rest.
+pub fn cross_fade_mask(size: Size, progress: f64) -> BezPath {
+    let covered = CROSS_FADE_STRIPE * progress.clamp(0.0, 1.0);
+    let mut mask = BezPath::new();
+    if covered <= 0.0 {
+        return mask;
+    }
+    let mut y = 0.0;
+    while y < size.height {
+        let stripe = Rect::new(0.0, y, size.width, (y + covered).min(size.height));
+        mask.extend(stripe.to_bez_path(0.0));
+        y += CROSS_FADE_STRIPE;
+    }
+    mask
+}
+
+/// A matcher's transition and the outgoing data while it runs.
+pub struct Animation<T> {
+    transition: Transition,
+    outgoing: Option<T>,
+    elapsed: Duration,
+}
+
+impl<T> Animation<T> {
+    pub fn new(transition: Transition) -> Self {
+        Animation {
+            transition,
+            outgoing: None,
+            elapsed: Duration::from_secs(0),
+        }
+    }
+
+    /// Whether switching variants animates at all.
+    pub fn is_animated(&self) -> bool {
+        self.transition.duration().is_some()
+    }
+
+    /// The data of the widget animating out, if a transition is running.
+    pub fn outgoing(&self) -> Option<&T> {
+        self.outgoing.as_ref()
+    }
+
+    /// Starts animating out the widget showing `outgoing`.
+    pub fn start(&mut self, outgoing: T) {
+        self.outgoing = Some(outgoing);
+        self.elapsed = Duration::from_secs(0);
+    }
+
+    /// Ends the running transition early, returning the data of the widget animating out.
+    pub fn stop(&mut self) -> Option<T> {
+        self.outgoing.take()
+    }
+
+    /// Advances the running transition by an animation frame, returning the data of the widget
+    /// animating out once it's done.
+    pub fn advance(&mut self, nanos: u64) -> Option<T> {
+        self.outgoing.as_ref()?;
+        self.elapsed += Duration::from_nanos(nanos);
+        if self.transition.progress(self.elapsed) < 1.0 {
+            None
+        } else {
+            self.outgoing.take()
+        }
+    }
+
+    /// Paints `widget` through the running transition, if any.
+    ///
+    /// `animation` gets the animation out of `widget`, and `paint` paints it for either the
+    /// outgoing or the current data.
+    pub fn paint<W>(
+        widget: &mut W,
+        ctx: &mut PaintCtx,
+        data: &T,
+        env: &Env,
+        animation: impl Fn(&mut W) -> &mut Self,
+        mut paint: impl FnMut(&mut W, &mut PaintCtx, &T, &Env),
+    ) {
+        let outgoing = match animation(widget).outgoing.take() {
+            Some(outgoing) => outgoing,
+            None => return paint(widget, ctx, data, env),
+        };
+        let transition = animation(widget).transition.clone();
+        let progress = transition.progress(animation(widget).elapsed);
+        transition.paint(ctx, env, progress, &mut |ctx, side| match side {
+            Side::Outgoing => paint(widget, ctx, &outgoing, env),
+            Side::Incoming => paint(widget, ctx, data, env),
+        });
+        animation(widget).outgoing = Some(outgoing);
+    }
+}
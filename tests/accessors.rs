@@ -0,0 +1,140 @@
+use druid::{Data, Lens};
+use druid_enums::Matcher;
+
+#[derive(Clone, Data, Lens, Debug, PartialEq)]
+struct LoginState {
+    user: String,
+}
+
+#[derive(Clone, Data, Matcher, Debug, PartialEq)]
+#[matcher(accessors)]
+enum AppState {
+    Login(LoginState),
+    Main {
+        user: String,
+        count: u32,
+    },
+    Pair(u32, u32),
+    #[matcher(builder_name = waiting)]
+    Loading,
+    Search(String),
+    Url(String),
+}
+
+fn login() -> AppState {
+    AppState::Login(LoginState {
+        user: String::from("user"),
+    })
+}
+
+#[test]
+fn is() {
+    assert!(login().is_login());
+    assert!(!login().is_main());
+    assert!(AppState::Loading.is_waiting());
+}
+
+#[test]
+fn as_ref() {
+    assert_eq!(
+        login().as_login().map(|login| login.user.as_str()),
+        Some("user")
+    );
+    assert_eq!(login().as_pair(), None);
+    assert_eq!(AppState::Pair(1, 2).as_pair(), Some((&1, &2)));
+    let main = AppState::Main {
+        user: String::from("user"),
+        count: 3,
+    };
+    assert_eq!(main.as_main(), Some((&String::from("user"), &3)));
+}
+
+#[test]
+fn as_mut() {
+    let mut state = AppState::Pair(1, 2);
+    if let Some((first, _)) = state.as_pair_mut() {
+        *first = 5;
+    }
+    assert_eq!(state, AppState::Pair(5, 2));
+    let mut state = login();
+    state.as_login_mut().unwrap().user.push('!');
+    assert_eq!(state.as_login().unwrap().user, "user!");
+}
+
+#[test]
+fn into() {
+    assert_eq!(
+        login().into_login(),
+        Some(LoginState {
+            user: String::from("user")
+        })
+    );
+    assert_eq!(AppState::Pair(1, 2).into_pair(), Some((1, 2)));
+    let main = AppState::Main {
+        user: String::from("user"),
+        count: 3,
+    }
+    .into_main()
+    .unwrap();
+    assert_eq!((main.user.as_str(), main.count), ("user", 3));
+    assert!(login().into_search().is_none());
+    assert_eq!(
+        AppState::Url(String::from("url")).into_url(),
+        Some(String::from("url"))
+    );
+}
+
+// Both `Search` and `Url` hold a `String`, so neither gets a `From` impl.
+#[test]
+fn no_from_for_shared_types() {
+    assert!(AppState::Search(String::new()).is_search());
+}
+
+#[test]
+fn from() {
+    let state: AppState = LoginState {
+        user: String::from("user"),
+    }
+    .into();
+    assert_eq!(state, login());
+    assert_eq!(AppState::from((1, 2)), AppState::Pair(1, 2));
+    let main = MainView {
+        user: String::from("user"),
+        count: 3,
+    };
+    assert!(AppState::from(main).is_main());
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+#[matcher(accessors)]
+enum Loadable<T> {
+    Loading,
+    Ready(T),
+    Failed(String),
+}
+
+#[test]
+fn generic() {
+    let ready = Loadable::Ready(3);
+    assert_eq!(ready.as_ready(), Some(&3));
+    assert!(!ready.is_loading());
+    // `Ready` gets no `From`, `T` may be a `String`.
+    let failed: Loadable<String> = String::from("offline").into();
+    assert!(failed.is_failed());
+    assert_eq!(failed.into_ready(), None);
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+#[matcher(accessors)]
+enum Pending<T> {
+    Waiting,
+    Done(T),
+}
+
+#[test]
+fn generic_from() {
+    let done: Pending<u32> = 3.into();
+    assert_eq!(done.as_done(), Some(&3));
+}
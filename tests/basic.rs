@@ -41,9 +41,35 @@ fn with_with_default() {
         .default_empty();
 }
 
+#[test]
+fn shared_with_default() {
+    AB::shared_matcher()
+        .a(SizedBox::<(String, A)>::empty())
+        .default(SizedBox::<(String, AB)>::empty());
+
+    AB::shared_matcher::<String>()
+        .b(SizedBox::<(String, B)>::empty())
+        .default_empty();
+}
+
 #[test]
 fn generated_matcher_name() {
     ABMatcher::new()
         .a(SizedBox::<A>::empty())
         .b(SizedBox::<B>::empty());
 }
+
+#[test]
+fn shared_return_type() {
+    fn inner() -> impl Widget<(String, AB)> {
+        AB::shared_matcher()
+    }
+    inner();
+}
+
+#[test]
+fn generated_shared_matcher_name() {
+    ABSharedMatcher::<String>::new()
+        .a(SizedBox::<(String, A)>::empty())
+        .b(SizedBox::<(String, B)>::empty());
+}
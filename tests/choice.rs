@@ -0,0 +1,57 @@
+use druid::{Data, Lens, Widget};
+use druid_enums::{choice, Matcher};
+
+#[derive(Clone, Copy, Data, Matcher, Debug, PartialEq)]
+enum Theme {
+    Light,
+    Dark,
+    #[matcher(label = "Follow the system")]
+    System,
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher, Debug, PartialEq)]
+enum Source {
+    Bundled,
+    #[matcher(default)]
+    File(String),
+    Url(String),
+}
+
+#[test]
+fn generated_widgets() {
+    fn radio_group() -> impl Widget<Theme> {
+        Theme::radio_group()
+    }
+    fn dropdown() -> impl Widget<Theme> {
+        Theme::dropdown()
+    }
+    radio_group();
+    dropdown();
+}
+
+#[test]
+fn generic_widgets() {
+    fn radio_group() -> impl Widget<Source> {
+        choice::radio_group()
+    }
+    fn dropdown() -> impl Widget<Source> {
+        choice::dropdown()
+    }
+    radio_group();
+    dropdown();
+}
+
+#[test]
+fn variant_index_lens() {
+    let mut theme = Theme::Light;
+    assert_eq!(choice::VariantIndex.with(&theme, |index| *index), 0);
+    choice::VariantIndex.with_mut(&mut theme, |index| *index = 2);
+    assert_eq!(theme, Theme::System);
+
+    let mut source = Source::Bundled;
+    choice::VariantIndex.with_mut(&mut source, |index| *index = 2);
+    assert_eq!(source, Source::Bundled);
+    choice::VariantIndex.with_mut(&mut source, |index| *index = 1);
+    assert_eq!(source, Source::File(String::new()));
+}
@@ -0,0 +1,93 @@
+use druid::Data;
+use druid_enums::{Matchable, Matcher};
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher, Debug, PartialEq)]
+enum Section {
+    Inbox,
+    #[matcher(default)]
+    Search(String),
+    #[matcher(default_data = (1, String::from("first")))]
+    Page(u32, String),
+    #[matcher(default)]
+    Settings {
+        dark: bool,
+        volume: u8,
+    },
+    #[matcher(default_data = ComposeView { to: String::from("me"), body: String::new() })]
+    Compose {
+        to: String,
+        body: String,
+    },
+    Closed(bool),
+}
+
+#[test]
+fn default_for() {
+    assert_eq!(Section::default_for(0), Some(Section::Inbox));
+    assert_eq!(
+        Section::default_for(1),
+        Some(Section::Search(String::new()))
+    );
+    assert_eq!(
+        Section::default_for(2),
+        Some(Section::Page(1, String::from("first")))
+    );
+    assert_eq!(
+        Section::default_for(3),
+        Some(Section::Settings {
+            dark: false,
+            volume: 0
+        })
+    );
+    assert_eq!(
+        Section::default_for(4),
+        Some(Section::Compose {
+            to: String::from("me"),
+            body: String::new()
+        })
+    );
+    assert_eq!(Section::default_for(5), None);
+}
+
+#[test]
+fn constructors() {
+    assert_eq!(Section::default_inbox(), Section::Inbox);
+    assert_eq!(Section::default_search(), Section::Search(String::new()));
+    assert_eq!(
+        Section::default_page(),
+        Section::Page(1, String::from("first"))
+    );
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Loadable<T> {
+    Loading,
+    #[matcher(default)]
+    Ready(T),
+}
+
+#[test]
+fn generic_default() {
+    assert!(matches!(
+        Loadable::<u32>::default_ready(),
+        Loadable::Ready(0)
+    ));
+}
+
+#[derive(Clone, Data, Debug, PartialEq)]
+struct NoDefault;
+
+#[test]
+fn generic_without_default() {
+    let ready = Loadable::Ready(NoDefault);
+    assert_eq!(ready.variant_index(), 1);
+    assert_eq!(Loadable::<NoDefault>::VARIANT_NAMES, &["Loading", "Ready"]);
+    assert!(matches!(
+        Loadable::<NoDefault>::default_for(0),
+        Some(Loadable::Loading)
+    ));
+    // Only `default_ready` can require `T: Default`.
+    assert!(Loadable::<u32>::default_for(1).is_none());
+}
@@ -0,0 +1,95 @@
+use druid::{
+    widget::{Label, SizedBox},
+    Data, Widget,
+};
+use druid_enums::Matcher;
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+#[matcher(exhaustive)]
+enum Screen {
+    Login(String),
+    Main { count: u32 },
+    Loading,
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+#[matcher(exhaustive, matcher_name = LoadableMatcher)]
+enum Loadable<T> {
+    Loading,
+    Ready(T),
+}
+
+#[test]
+fn every_variant_set() {
+    fn inner() -> impl Widget<Screen> {
+        Screen::matcher()
+            .loading(Label::new("Loading"))
+            .login(SizedBox::empty())
+            .main_with(SizedBox::empty)
+            .keep_alive()
+    }
+    inner();
+}
+
+#[test]
+fn default_covers_missing_variants() {
+    fn inner() -> impl Widget<(u32, Screen)> {
+        Screen::shared_matcher()
+            .login(SizedBox::empty())
+            .default_empty()
+    }
+    inner();
+}
+
+#[test]
+fn generic_exhaustive() {
+    fn inner() -> impl Widget<Loadable<u32>> {
+        LoadableMatcher::new()
+            .ready(SizedBox::empty())
+            .loading(SizedBox::empty())
+    }
+    inner();
+}
+
+#[test]
+fn unchecked() {
+    fn inner() -> impl Widget<Screen> {
+        Screen::matcher().login(SizedBox::empty()).unchecked()
+    }
+    inner();
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+#[matcher(exhaustive)]
+enum Choice {
+    #[matcher(builder_name = standard)]
+    Default,
+    Custom(u32),
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+#[matcher(exhaustive, matcher_name = PairMatcher)]
+enum Pair<Shared, Left_> {
+    Left(Left_),
+    Shared(Shared),
+}
+
+#[test]
+fn state_params_dont_collide() {
+    fn default_variant() -> impl Widget<(u32, Choice)> {
+        Choice::shared_matcher()
+            .standard(SizedBox::empty())
+            .custom(SizedBox::empty())
+    }
+    fn generic_params() -> impl Widget<(bool, Pair<u32, String>)> {
+        Pair::shared_matcher()
+            .left(SizedBox::empty())
+            .shared(SizedBox::empty())
+    }
+    default_variant();
+    generic_params();
+}
@@ -0,0 +1,46 @@
+use druid::{widget::SizedBox, Data, Widget};
+use druid_enums::Matcher;
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Loadable<T> {
+    Loading(()),
+    Ready(T),
+    Failed(String),
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Tagged<Shared> {
+    Value(Shared),
+}
+
+#[test]
+fn generic_matcher() {
+    fn inner() -> impl Widget<Loadable<u32>> {
+        Loadable::matcher()
+            .loading(SizedBox::empty())
+            .ready(SizedBox::<u32>::empty())
+            .failed(SizedBox::empty())
+    }
+    inner();
+}
+
+#[test]
+fn generic_shared_matcher() {
+    fn inner() -> impl Widget<(String, Loadable<u32>)> {
+        Loadable::shared_matcher()
+            .loading(SizedBox::empty())
+            .ready(SizedBox::<(String, u32)>::empty())
+            .failed(SizedBox::empty())
+    }
+    inner();
+}
+
+#[test]
+fn generic_param_named_shared() {
+    fn inner() -> impl Widget<(u32, Tagged<String>)> {
+        Tagged::shared_matcher().value(SizedBox::<(u32, String)>::empty())
+    }
+    inner();
+}
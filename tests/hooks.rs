@@ -0,0 +1,67 @@
+use druid::{widget::SizedBox, Data, Widget};
+use druid_enums::Matcher;
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Screen {
+    Login(String),
+    Main { user: String, count: u32 },
+    Loading,
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+#[matcher(exhaustive)]
+enum Checked {
+    Ready(u32),
+    Empty,
+}
+
+// These only check that hooks of each shape can be set. When they run depends on update passes
+// druid 0.6 only runs inside a window.
+#[test]
+fn hooks() {
+    fn inner() -> impl Widget<Screen> {
+        Screen::matcher()
+            .login(SizedBox::empty())
+            .main(SizedBox::empty())
+            .loading(SizedBox::empty())
+            .on_enter(Screen::MAIN, |ctx, main: &MainView, _env| {
+                if main.count == 0 {
+                    ctx.request_layout();
+                }
+            })
+            .on_exit(Screen::LOGIN, |_ctx, user: &String, _env| {
+                let _ = user.len();
+            })
+            .on_change(|_ctx, old: &Screen, new: &Screen, _env| {
+                let _ = (old.clone(), new.clone());
+            })
+    }
+    inner();
+}
+
+#[test]
+fn shared_hooks() {
+    fn inner() -> impl Widget<(u32, Screen)> {
+        Screen::shared_matcher().default_empty().on_enter(
+            Screen::LOADING,
+            |_ctx, (shared, ()): &(u32, ()), _env| {
+                let _ = shared + 1;
+            },
+        )
+    }
+    inner();
+}
+
+#[test]
+fn exhaustive_hooks() {
+    fn inner() -> impl Widget<Checked> {
+        Checked::matcher()
+            .ready(SizedBox::empty())
+            .empty(SizedBox::empty())
+            .on_enter(Checked::READY, |_ctx, _value: &u32, _env| ())
+            .on_change(|_ctx, _old, _new, _env| ())
+    }
+    inner();
+}
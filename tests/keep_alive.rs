@@ -0,0 +1,41 @@
+use druid::{
+    widget::{Label, SizedBox},
+    Data, Widget,
+};
+use druid_enums::Matcher;
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Screen {
+    Login(String),
+    Main {
+        count: u32,
+    },
+    #[matcher(rebuild_on_enter)]
+    Settings,
+}
+
+// These only check that keep-alive matchers build. Whether hidden widgets get commands, timers
+// and lifecycle events depends on passes druid 0.6 only runs inside a window.
+#[test]
+fn keep_alive() {
+    fn inner() -> impl Widget<Screen> {
+        Screen::matcher()
+            .login(SizedBox::empty())
+            .main(SizedBox::empty())
+            .settings_with(|| Label::new("Settings"))
+            .keep_alive()
+    }
+    inner();
+}
+
+#[test]
+fn keep_alive_shared() {
+    fn inner() -> impl Widget<(u32, Screen)> {
+        Screen::shared_matcher()
+            .login(SizedBox::empty())
+            .default_empty()
+            .keep_alive()
+    }
+    inner();
+}
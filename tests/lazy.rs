@@ -0,0 +1,66 @@
+use druid::{
+    widget::{Label, SizedBox},
+    Data, Widget,
+};
+use druid_enums::Matcher;
+use std::{cell::Cell, rc::Rc};
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Screen {
+    Login(String),
+    #[matcher(rebuild_on_enter)]
+    Main(u32),
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+#[matcher(rebuild_on_enter)]
+enum Rebuilt {
+    A(u32),
+    B(u32),
+}
+
+#[test]
+fn lazy_builders() {
+    fn inner() -> impl Widget<Screen> {
+        Screen::matcher()
+            .login_with(|| Label::new("Login"))
+            .main_with(SizedBox::empty)
+    }
+    inner();
+}
+
+#[test]
+fn lazy_and_eager_builders() {
+    fn inner() -> impl Widget<(String, Screen)> {
+        Screen::shared_matcher()
+            .login(SizedBox::empty())
+            .main_with(SizedBox::empty)
+    }
+    inner();
+}
+
+// A factory counting how many times it builds its widget.
+fn counted<T: Data>(builds: &Rc<Cell<u32>>) -> impl FnMut() -> SizedBox<T> {
+    let builds = builds.clone();
+    move || {
+        builds.set(builds.get() + 1);
+        SizedBox::empty()
+    }
+}
+
+// The factories only run once a widget is entered, which takes a running window to test.
+#[test]
+fn not_built_with_the_matcher() {
+    let builds = Rc::new(Cell::new(0));
+    let matcher = Screen::matcher()
+        .login_with(counted(&builds))
+        .main_with(counted(&builds));
+    let shared_matcher = Rebuilt::shared_matcher::<u32>()
+        .a_with(counted(&builds))
+        .b_with(counted(&builds));
+    assert_eq!(builds.get(), 0);
+    drop((matcher, shared_matcher));
+    assert_eq!(builds.get(), 0);
+}
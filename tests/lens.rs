@@ -0,0 +1,47 @@
+use druid::{Data, Lens};
+use druid_enums::{ChainLens, LensZipExt};
+use std::sync::Arc;
+
+#[derive(Clone, Data, Lens)]
+struct State {
+    shared: Arc<String>,
+    count: u32,
+}
+
+fn state() -> State {
+    State {
+        shared: Arc::new(String::from("shared")),
+        count: 0,
+    }
+}
+
+#[test]
+fn with() {
+    let lens = ChainLens::new(State::shared, State::count);
+    let (shared, count) = lens.with(&state(), |data| data.clone());
+    assert_eq!(*shared, "shared");
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn with_mut() {
+    let mut data = state();
+    State::shared
+        .zip(State::count)
+        .with_mut(&mut data, |(shared, count)| {
+            *shared = Arc::new(String::from("changed"));
+            *count += 1;
+        });
+    assert_eq!(*data.shared, "changed");
+    assert_eq!(data.count, 1);
+}
+
+#[test]
+fn unchanged_parts_are_kept() {
+    let mut data = state();
+    let shared = data.shared.clone();
+    State::shared
+        .zip(State::count)
+        .with_mut(&mut data, |(_, count)| *count += 1);
+    assert!(Arc::ptr_eq(&shared, &data.shared));
+}
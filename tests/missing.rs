@@ -0,0 +1,52 @@
+use druid::{widget::SizedBox, Data, Widget, WidgetId};
+use druid_enums::{matcher, Matcher, Missing};
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Screen {
+    Login(String),
+    Main(u32),
+}
+
+#[test]
+fn on_missing() {
+    fn inner() -> impl Widget<Screen> {
+        Screen::matcher()
+            .login(SizedBox::empty())
+            .on_missing(Missing::Placeholder)
+    }
+    inner();
+}
+
+#[test]
+fn default_policy() {
+    let expected = if cfg!(debug_assertions) {
+        Missing::Panic
+    } else {
+        Missing::Warn
+    };
+    assert_eq!(Missing::default(), expected);
+}
+
+#[test]
+fn warn_and_placeholder() {
+    let id = WidgetId::next();
+    assert!(!matcher::missing(
+        Missing::Warn,
+        "ScreenMatcher",
+        "main",
+        id
+    ));
+    assert!(matcher::missing(
+        Missing::Placeholder,
+        "ScreenMatcher",
+        "main",
+        id
+    ));
+}
+
+#[test]
+#[should_panic(expected = "ScreenMatcher::main variant")]
+fn panic() {
+    matcher::missing(Missing::Panic, "ScreenMatcher", "main", WidgetId::next());
+}
@@ -0,0 +1,121 @@
+use druid::{
+    widget::{SizedBox, TextBox},
+    Data, Widget, WidgetExt,
+};
+use druid_enums::Matcher;
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Document {
+    Editing {
+        doc: String,
+        cursor: usize,
+    },
+    #[matcher(view_name = ReadOnly)]
+    Viewing {
+        doc: String,
+    },
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Loadable<T> {
+    Ready { value: T, fetched: u64 },
+    Failed(String),
+}
+
+#[test]
+fn view_struct_lenses() {
+    fn inner() -> impl Widget<Document> {
+        Document::matcher()
+            .editing(TextBox::new().lens(EditingView::doc))
+            .viewing(SizedBox::<ReadOnly>::empty())
+    }
+    inner();
+}
+
+#[test]
+fn view_struct_fields() {
+    let view = EditingView {
+        doc: String::from("text"),
+        cursor: 2,
+    };
+    assert!(view.same(&view.clone()));
+}
+
+#[test]
+fn generic_view_struct() {
+    fn inner() -> impl Widget<Loadable<String>> {
+        Loadable::matcher()
+            .ready(SizedBox::<ReadyView<String>>::empty())
+            .failed(SizedBox::empty())
+    }
+    inner();
+}
+
+trait Marker: Clone + PartialEq + 'static {}
+
+impl Marker for u8 {}
+
+// Druid can't derive `Data` with bounds.
+#[derive(Clone)]
+struct Wrap<T: Marker>(T);
+
+impl<T: Marker> Data for Wrap<T> {
+    fn same(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Matcher)]
+enum Bounded<T: Marker, U>
+where
+    U: Marker,
+{
+    V { x: Wrap<T> },
+    W { y: Wrap<U> },
+    Other,
+}
+
+impl<T: Marker, U: Marker> Data for Bounded<T, U> {
+    fn same(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Bounded::V { x }, Bounded::V { x: other }) => x.same(other),
+            (Bounded::W { y }, Bounded::W { y: other }) => y.same(other),
+            (Bounded::Other, Bounded::Other) => true,
+            _ => false,
+        }
+    }
+}
+
+#[test]
+fn bounded_view_struct() {
+    fn inner() -> impl Widget<Bounded<u8, u8>> {
+        Bounded::matcher()
+            .v(SizedBox::<VView<u8>>::empty())
+            .w(SizedBox::<WView<u8>>::empty())
+            .other(SizedBox::empty())
+    }
+    inner();
+    assert!(WView { y: Wrap(1) }.same(&WView { y: Wrap(1) }));
+    assert!(!WView { y: Wrap(1) }.same(&WView { y: Wrap(2) }));
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Cached<T> {
+    Hit {
+        value: T,
+        #[data(ignore)]
+        at: u64,
+    },
+    Miss,
+}
+
+#[test]
+fn generic_view_struct_attributes() {
+    let hit = HitView { value: 1, at: 2 };
+    assert!(hit.same(&HitView { value: 1, at: 3 }));
+    assert!(!hit.same(&HitView { value: 2, at: 2 }));
+}
@@ -0,0 +1,92 @@
+use druid::{widget::SizedBox, Data, Widget};
+use druid_enums::{Matchable, Matcher, Prism};
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+#[matcher(accessors, selectors)]
+enum Token {
+    Type(String),
+    Match,
+    Crate(u32),
+    Fn { arity: u32 },
+}
+
+#[test]
+fn keyword_builders() {
+    fn inner() -> impl Widget<Token> {
+        Token::matcher()
+            .r#type(SizedBox::empty())
+            .r#match(SizedBox::empty())
+            .crate_(SizedBox::empty())
+            .fn_with(SizedBox::empty)
+    }
+    inner();
+}
+
+#[test]
+fn keyword_accessors() {
+    let token = Token::Type(String::from("u32"));
+    assert!(token.is_type());
+    assert_eq!(token.as_type().map(String::as_str), Some("u32"));
+    assert!(Token::Crate(1).is_crate());
+    assert!(Token::TYPE.with(&token, |_| ()).is_some());
+    let _ = Token::GOTO_MATCH;
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Grade {
+    A(u32),
+    B,
+}
+
+#[test]
+fn prism_named_like_a_variant() {
+    assert_eq!(Grade::A_.with(&Grade::A(1), |a| *a), Some(1));
+    assert_eq!(Grade::B_.with(&Grade::A(1), |_| ()), None);
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Failure {
+    HTTPError(u16),
+    #[matcher(builder_name = http_error_2)]
+    HttpError(u16),
+    #[matcher(builder_name = fresh)]
+    New,
+}
+
+#[test]
+fn renamed_collisions() {
+    fn inner() -> impl Widget<Failure> {
+        Failure::matcher()
+            .http_error(SizedBox::empty())
+            .http_error_2(SizedBox::empty())
+            .fresh(SizedBox::empty())
+    }
+    inner();
+    assert!(Failure::HTTP_ERROR_2
+        .with(&Failure::HttpError(404), |_| ())
+        .is_some());
+}
+
+#[allow(dead_code, non_camel_case_types)]
+#[derive(Clone, Data, Matcher)]
+#[matcher(exhaustive)]
+enum Keyword {
+    r#match { arms: u32 },
+    r#type(String),
+}
+
+#[test]
+fn raw_variant_names() {
+    fn inner() -> impl Widget<Keyword> {
+        Keyword::matcher()
+            .r#match(SizedBox::<MatchView>::empty())
+            .r#type(SizedBox::empty())
+    }
+    inner();
+    assert_eq!(Keyword::VARIANT_NAMES, &["match", "type"]);
+    assert_eq!(Keyword::VARIANT_LABELS, &["match", "type"]);
+    assert_eq!(Keyword::r#match { arms: 2 }.variant_name(), "match");
+}
@@ -0,0 +1,102 @@
+use druid::{Data, Widget};
+use druid_enums::{navigator::History, Matcher, Navigator};
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher, Debug, PartialEq)]
+enum Step {
+    Start,
+    Details(String),
+    Done,
+}
+
+#[test]
+fn navigator() {
+    fn inner() -> impl Widget<Step> {
+        Navigator::new(Step::matcher().default_empty()).max_depth(10)
+    }
+    inner();
+}
+
+#[test]
+fn shared_navigator() {
+    fn inner() -> impl Widget<(u32, Step)> {
+        Navigator::shared(Step::shared_matcher().default_empty())
+    }
+    inner();
+}
+
+#[test]
+fn back_and_forward() {
+    let mut history = History::new();
+    let mut current = Step::Done;
+    history.push(Step::Start);
+    history.push(Step::Details(String::from("name")));
+    assert!(history.can_go_back());
+    assert!(!history.can_go_forward());
+
+    assert!(history.back(&mut current));
+    assert_eq!(current, Step::Details(String::from("name")));
+    assert!(history.back(&mut current));
+    assert_eq!(current, Step::Start);
+    assert!(!history.back(&mut current));
+
+    assert!(history.forward(&mut current));
+    assert_eq!(current, Step::Details(String::from("name")));
+    assert!(history.can_go_forward());
+}
+
+#[test]
+fn push_clears_forward() {
+    let mut history = History::new();
+    let mut current = Step::Details(String::new());
+    history.push(Step::Start);
+    history.back(&mut current);
+    history.push(Step::Start);
+    assert!(!history.can_go_forward());
+}
+
+#[test]
+fn max_depth() {
+    let mut history = History::new();
+    history.set_max_depth(1);
+    history.push(Step::Start);
+    history.push(Step::Details(String::new()));
+    let mut current = Step::Done;
+    assert!(history.back(&mut current));
+    assert_eq!(current, Step::Details(String::new()));
+    assert!(!history.back(&mut current));
+}
+
+#[test]
+fn navigating_isnt_recorded() {
+    let mut history = History::new();
+    let mut current = Step::Done;
+    history.push(Step::Start);
+    assert!(history.back(&mut current));
+    assert!(!history.record(&Step::Done, &current));
+    assert!(!history.can_go_back());
+    assert!(history.can_go_forward());
+}
+
+// Going to a value that is the same as the current one doesn't `update` the navigator, the next
+// switch is still recorded.
+#[test]
+fn switch_after_navigating_in_place() {
+    let mut history = History::new();
+    let mut current = Step::Start;
+    history.push(Step::Start);
+    assert!(history.back(&mut current));
+    assert!(history.record(&Step::Start, &Step::Done));
+    assert!(history.can_go_back());
+
+    history.replace(&mut current, Step::Start);
+    assert!(history.record(&Step::Start, &Step::Done));
+}
+
+#[test]
+fn edits_arent_recorded() {
+    let mut history = History::new();
+    let old = Step::Details(String::from("a"));
+    assert!(!history.record(&old, &Step::Details(String::from("ab"))));
+    assert!(!history.can_go_back());
+}
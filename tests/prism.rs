@@ -0,0 +1,87 @@
+use druid::{
+    widget::{Label, TextBox},
+    Data, Lens, Widget,
+};
+use druid_enums::{Matcher, Prism, PrismExt, PrismWidgetExt};
+
+#[derive(Clone, Data, Lens)]
+struct LoginState {
+    user: String,
+}
+
+#[derive(Clone, Data, Matcher)]
+enum AppState {
+    Login(LoginState),
+    Main { user: String, count: u32 },
+    Pair(u32, u32),
+    Loading,
+}
+
+#[derive(Clone, Data, Lens)]
+struct Window {
+    state: AppState,
+}
+
+fn login() -> AppState {
+    AppState::Login(LoginState {
+        user: String::from("user"),
+    })
+}
+
+#[test]
+fn with() {
+    assert_eq!(
+        AppState::LOGIN.with(&login(), |login| login.user.clone()),
+        Some(String::from("user"))
+    );
+    assert_eq!(AppState::LOADING.with(&login(), |_| ()), None);
+    assert_eq!(AppState::LOADING.with(&AppState::Loading, |_| ()), Some(()));
+    assert_eq!(
+        AppState::PAIR.with(&AppState::Pair(1, 2), |pair| pair.1),
+        Some(2)
+    );
+}
+
+#[test]
+fn with_mut() {
+    let mut data = AppState::Main {
+        user: String::from("user"),
+        count: 1,
+    };
+    AppState::MAIN.with_mut(&mut data, |main| main.count += 1);
+    assert!(matches!(data, AppState::Main { count: 2, .. }));
+    assert_eq!(AppState::LOGIN.with_mut(&mut data, |_| ()), None);
+}
+
+#[test]
+fn then_lens() {
+    let mut data = login();
+    AppState::LOGIN
+        .then(LoginState::user)
+        .with_mut(&mut data, |user| user.push('s'));
+    assert_eq!(
+        AppState::LOGIN
+            .then(LoginState::user)
+            .with(&data, String::clone),
+        Some(String::from("users"))
+    );
+}
+
+#[test]
+fn after_lens() {
+    let window = Window { state: login() };
+    let prism = AppState::LOGIN.after(Window::state);
+    assert_eq!(prism.with(&window, |login| login.user.len()), Some(4));
+}
+
+#[test]
+fn prism_widget() {
+    fn inner() -> impl Widget<AppState> {
+        TextBox::new().prism(AppState::LOGIN.then(LoginState::user))
+    }
+    fn unit() -> impl Widget<AppState> {
+        Label::new("Loading").prism(AppState::LOADING)
+    }
+    inner();
+    unit();
+}
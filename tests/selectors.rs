@@ -0,0 +1,64 @@
+use druid::{widget::SizedBox, Data, Selector, Widget};
+use druid_enums::Matcher;
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+#[matcher(selectors)]
+enum Screen {
+    Login(String),
+    Main { user: String, count: u32 },
+    Pair(u32, u32),
+    Loading,
+}
+
+#[test]
+fn selector_payloads() {
+    let _: Selector<String> = Screen::GOTO_LOGIN;
+    let _: Selector<MainView> = Screen::GOTO_MAIN;
+    let _: Selector<(u32, u32)> = Screen::GOTO_PAIR;
+    let _: Selector<()> = Screen::GOTO_LOADING;
+}
+
+#[test]
+fn selector_commands() {
+    let command = Screen::GOTO_MAIN.with(MainView {
+        user: String::from("user"),
+        count: 0,
+    });
+    assert!(command.is(Screen::GOTO_MAIN));
+    assert!(!command.is(Screen::GOTO_LOGIN));
+}
+
+mod other {
+    use druid::Data;
+    use druid_enums::Matcher;
+
+    #[allow(dead_code)]
+    #[derive(Clone, Data, Matcher)]
+    #[matcher(selectors)]
+    pub enum Screen {
+        Login(u32),
+    }
+}
+
+// A selector with the same key but another payload type would make `Command::get` panic in the
+// matcher. Payload types are checked when building commands, see `ui/selector_payload.rs`, and
+// keys include the module.
+#[test]
+fn selectors_of_enums_with_the_same_name() {
+    let command = other::Screen::GOTO_LOGIN.with(1);
+    assert!(!command.is(Screen::GOTO_LOGIN));
+    assert_eq!(command.get(Screen::GOTO_LOGIN), None);
+}
+
+// This only checks that the matcher builds. Switching variants on the command needs an event
+// pass, which druid 0.6 only runs inside a window.
+#[test]
+fn matcher_with_selectors() {
+    fn inner() -> impl Widget<(u32, Screen)> {
+        Screen::shared_matcher()
+            .login(SizedBox::empty())
+            .default_empty()
+    }
+    inner();
+}
@@ -0,0 +1,53 @@
+use druid::{widget::SizedBox, Data, Widget};
+use druid_enums::Matcher;
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Shapes {
+    Unit,
+    Single(u32),
+    Pair(u32, String),
+    Triple(u32, String, bool),
+    Named { a: u32, b: String },
+}
+
+// `#[derive(Data)]` doesn't support empty tuple variants.
+#[allow(dead_code)]
+#[derive(Clone, Matcher)]
+enum EmptyTuple {
+    Empty(),
+    Other(u32),
+}
+
+impl Data for EmptyTuple {
+    fn same(&self, other: &Self) -> bool {
+        match (self, other) {
+            (EmptyTuple::Empty(), EmptyTuple::Empty()) => true,
+            (EmptyTuple::Other(a), EmptyTuple::Other(b)) => a.same(b),
+            _ => false,
+        }
+    }
+}
+
+#[test]
+fn all_shapes() {
+    fn inner() -> impl Widget<Shapes> {
+        Shapes::matcher()
+            .unit(SizedBox::<()>::empty())
+            .single(SizedBox::<u32>::empty())
+            .pair(SizedBox::<(u32, String)>::empty())
+            .triple(SizedBox::<(u32, String, bool)>::empty())
+            .named(SizedBox::<NamedView>::empty())
+    }
+    inner();
+}
+
+#[test]
+fn empty_tuple() {
+    fn inner() -> impl Widget<EmptyTuple> {
+        EmptyTuple::matcher()
+            .empty(SizedBox::<()>::empty())
+            .other(SizedBox::<u32>::empty())
+    }
+    inner();
+}
@@ -0,0 +1,41 @@
+use druid::{widget::SizedBox, Data, Widget};
+use druid_enums::{Matchable, Matcher, Tabs};
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Section {
+    #[matcher(label = "Inbox (new)")]
+    Inbox,
+    Archive,
+    Search(String),
+}
+
+#[test]
+fn tabs() {
+    fn inner() -> impl Widget<Section> {
+        Tabs::new(
+            Section::matcher()
+                .inbox(SizedBox::empty())
+                .archive(SizedBox::empty())
+                .search(SizedBox::empty()),
+        )
+    }
+    inner();
+}
+
+#[test]
+fn labels() {
+    assert_eq!(
+        Section::VARIANT_LABELS,
+        &["Inbox (new)", "Archive", "Search"]
+    );
+    assert_eq!(Section::VARIANT_NAMES, &["Inbox", "Archive", "Search"]);
+}
+
+#[test]
+fn unit_variants_have_defaults() {
+    assert!(matches!(Section::default_for(0), Some(Section::Inbox)));
+    assert!(matches!(Section::default_for(1), Some(Section::Archive)));
+    assert!(Section::default_for(2).is_none());
+    assert!(Section::default_for(3).is_none());
+}
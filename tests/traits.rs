@@ -0,0 +1,66 @@
+use druid::{widget::SizedBox, Data, Widget};
+use druid_enums::{matcher, Matchable, Matcher};
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Loadable<T> {
+    Loading,
+    Ready(T),
+}
+
+fn generic_matcher<E: matcher::Matcher>() -> impl Widget<E> {
+    E::matcher()
+}
+
+fn generic_shared_matcher<E: matcher::SharedMatcher<String>>() -> impl Widget<(String, E)> {
+    E::shared_matcher()
+}
+
+#[test]
+fn matcher_traits() {
+    generic_matcher::<Loadable<u32>>();
+    generic_shared_matcher::<Loadable<u32>>();
+}
+
+#[test]
+fn trait_matcher_is_the_generated_one() {
+    fn inner() -> impl Widget<Loadable<u32>> {
+        <Loadable<u32> as matcher::Matcher>::matcher()
+            .loading(SizedBox::empty())
+            .ready(SizedBox::empty())
+    }
+    inner();
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Shape {
+    Point,
+    Circle(f64),
+    Rect { width: f64, height: f64 },
+}
+
+fn describe<E: Matchable>(value: &E) -> String {
+    format!(
+        "{}/{} {}",
+        value.variant_index() + 1,
+        E::VARIANT_COUNT,
+        value.variant_name()
+    )
+}
+
+#[test]
+fn variant_metadata() {
+    assert_eq!(Shape::VARIANT_NAMES, &["Point", "Circle", "Rect"]);
+    assert_eq!(Shape::VARIANT_COUNT, 3);
+    assert_eq!(Shape::Point.variant_index(), 0);
+    assert_eq!(Shape::Circle(1.0).variant_name(), "Circle");
+    assert_eq!(
+        describe(&Shape::Rect {
+            width: 1.0,
+            height: 2.0
+        }),
+        "3/3 Rect"
+    );
+    assert_eq!(Loadable::Ready(1u32).variant_name(), "Ready");
+}
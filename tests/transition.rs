@@ -0,0 +1,89 @@
+use druid::{kurbo::Shape, widget::SizedBox, Data, Size, Widget};
+use druid_enums::{
+    transition::{cross_fade_mask, Side, CROSS_FADE_STRIPE},
+    Matcher, Transition,
+};
+use std::time::Duration;
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Screen {
+    Login(String),
+    #[matcher(rebuild_on_enter)]
+    Main(u32),
+}
+
+#[test]
+fn transition() {
+    fn inner() -> impl Widget<(String, Screen)> {
+        Screen::shared_matcher()
+            .login(SizedBox::empty())
+            .main_with(SizedBox::empty)
+            .transition(Transition::SlideLeft(Duration::from_millis(250)))
+    }
+    inner();
+}
+
+#[test]
+fn custom_transition() {
+    fn inner() -> impl Widget<Screen> {
+        Screen::matcher()
+            .login(SizedBox::empty())
+            .main(SizedBox::empty())
+            .transition(Transition::custom(
+                Duration::from_millis(250),
+                |ctx, _env, progress, paint| {
+                    if progress < 0.5 {
+                        paint(ctx, Side::Outgoing)
+                    } else {
+                        paint(ctx, Side::Incoming)
+                    }
+                },
+            ))
+    }
+    inner();
+}
+
+#[test]
+fn progress() {
+    let transition = Transition::CrossFade(Duration::from_millis(200));
+    assert_eq!(transition.duration(), Some(Duration::from_millis(200)));
+    assert_eq!(transition.progress(Duration::from_millis(50)), 0.25);
+    assert_eq!(transition.progress(Duration::from_millis(300)), 1.0);
+}
+
+#[test]
+fn no_animation() {
+    assert_eq!(Transition::None.duration(), None);
+    assert_eq!(
+        Transition::SlideRight(Duration::from_secs(0)).duration(),
+        None
+    );
+    assert_eq!(Transition::None.progress(Duration::from_secs(0)), 1.0);
+}
+
+#[test]
+fn cross_fade_covers_both_widgets() {
+    let size = Size::new(100.0, 10.0 * CROSS_FADE_STRIPE);
+    let area = size.width * size.height;
+    let incoming = |progress| cross_fade_mask(size, progress).area().abs();
+
+    assert_eq!(incoming(0.0), 0.0);
+    // Midway, the incoming widget covers half of the area and the outgoing one the other half.
+    assert!((incoming(0.5) - area / 2.0).abs() < 1e-6);
+    assert!((incoming(0.25) - area / 4.0).abs() < 1e-6);
+    assert!((incoming(1.0) - area).abs() < 1e-6);
+    // Every stripe shows some of both widgets.
+    let mask = cross_fade_mask(size, 0.5);
+    for stripe in 0..10 {
+        let top = stripe as f64 * CROSS_FADE_STRIPE;
+        assert_ne!(
+            mask.winding((50.0, top + 0.25 * CROSS_FADE_STRIPE).into()),
+            0
+        );
+        assert_eq!(
+            mask.winding((50.0, top + 0.75 * CROSS_FADE_STRIPE).into()),
+            0
+        );
+    }
+}
@@ -0,0 +1,11 @@
+use druid::Data;
+use druid_enums::Matcher;
+
+#[derive(Clone, Data, Matcher)]
+#[matcher(selectors)]
+enum Nav {
+    Main(u32),
+    GotoMain(u32),
+}
+
+fn main() {}
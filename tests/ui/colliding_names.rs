@@ -0,0 +1,23 @@
+use druid::Data;
+use druid_enums::Matcher;
+
+#[derive(Clone, Data, Matcher)]
+#[matcher(accessors)]
+enum Failure {
+    HTTPError(u16),
+    HttpError(u16),
+    New,
+    For,
+}
+
+#[derive(Clone, Data, Matcher)]
+enum Screen {
+    #[matcher(view_name = Fields)]
+    Login { user: String },
+    #[matcher(view_name = Fields)]
+    Main { count: u32 },
+    #[matcher(view_name = ScreenMatcher)]
+    Settings { dark: bool },
+}
+
+fn main() {}
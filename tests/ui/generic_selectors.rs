@@ -0,0 +1,11 @@
+use druid::Data;
+use druid_enums::Matcher;
+
+#[derive(Clone, Data, Matcher)]
+#[matcher(selectors)]
+enum Loadable<T> {
+    Loading,
+    Ready(T),
+}
+
+fn main() {}
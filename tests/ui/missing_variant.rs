@@ -0,0 +1,13 @@
+use druid::{widget::SizedBox, Data, Widget};
+use druid_enums::Matcher;
+
+#[derive(Clone, Data, Matcher)]
+#[matcher(exhaustive)]
+enum Screen {
+    Login(String),
+    Main(u32),
+}
+
+fn main() {
+    let _: Box<dyn Widget<Screen>> = Box::new(Screen::matcher().login(SizedBox::empty()));
+}
@@ -0,0 +1,13 @@
+use druid::Data;
+use druid_enums::Matcher;
+
+#[derive(Clone, Data, Matcher)]
+#[matcher(selectors)]
+enum Screen {
+    Login(String),
+    Loading,
+}
+
+fn main() {
+    let _ = Screen::GOTO_LOGIN.with(42u32);
+}
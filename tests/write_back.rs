@@ -0,0 +1,197 @@
+use druid::{
+    widget::{Label, SizedBox},
+    Data, Env, Lens, Widget,
+};
+use druid_enums::{Matcher, Prism, PrismExt};
+use std::sync::Arc;
+
+#[derive(Clone, Data)]
+struct Shared {
+    history: Arc<Vec<String>>,
+    count: u32,
+}
+
+#[derive(Clone, Data, Matcher)]
+enum Screen {
+    Editor {
+        path: String,
+        lines: Arc<Vec<String>>,
+    },
+    Empty,
+}
+
+fn data() -> (Shared, Screen) {
+    (
+        Shared {
+            history: Arc::new(vec![String::from("opened")]),
+            count: 0,
+        },
+        Screen::Editor {
+            path: String::from("src/lib.rs"),
+            lines: Arc::new(vec![String::from("line")]),
+        },
+    )
+}
+
+fn lines(screen: &Screen) -> &Arc<Vec<String>> {
+    match screen {
+        Screen::Editor { lines, .. } => lines,
+        Screen::Empty => panic!("not the editor"),
+    }
+}
+
+// What a matcher does around passing an event to the widget of a variant.
+#[test]
+fn matcher_keeps_unchanged_data() {
+    let mut matcher = Screen::shared_matcher()
+        .editor(SizedBox::empty())
+        .empty(SizedBox::empty());
+    let mut data = data();
+    let (history, old_lines) = (data.0.history.clone(), lines(&data.1).clone());
+
+    matcher.project_(&data);
+    let seen = matcher.editor_data_.as_mut().unwrap();
+    seen.0.count += 1;
+    seen.1.path.push('~');
+    matcher.write_back_(&mut data);
+
+    assert_eq!(data.0.count, 1);
+    assert!(Arc::ptr_eq(&data.0.history, &history));
+    assert!(Arc::ptr_eq(lines(&data.1), &old_lines));
+    assert!(matches!(&data.1, Screen::Editor { path, .. } if path == "src/lib.rs~"));
+}
+
+#[test]
+fn matcher_leaves_unchanged_variant_alone() {
+    let mut matcher = Screen::shared_matcher()
+        .editor(SizedBox::empty())
+        .empty(SizedBox::empty());
+    let mut data = data();
+    let path = match &data.1 {
+        Screen::Editor { path, .. } => path.as_ptr(),
+        Screen::Empty => unreachable!(),
+    };
+
+    matcher.project_(&data);
+    matcher.editor_data_.as_mut().unwrap().0.count += 1;
+    matcher.write_back_(&mut data);
+
+    assert_eq!(data.0.count, 1);
+    assert!(matches!(&data.1, Screen::Editor { path: new_path, .. } if new_path.as_ptr() == path));
+}
+
+#[test]
+fn matcher_reuses_unchanged_data() {
+    let mut matcher = Screen::shared_matcher()
+        .editor(SizedBox::empty())
+        .empty(SizedBox::empty());
+    let data = data();
+    matcher.project_(&data);
+    let seen = matcher.editor_data_.clone().unwrap();
+
+    let mut changed = data.clone();
+    changed.0.count = 2;
+    matcher.project_(&changed);
+    let seen_again = matcher.editor_data_.as_ref().unwrap();
+    assert_eq!(seen_again.0.count, 2);
+    assert!(seen.1.lines.same(&seen_again.1.lines));
+    assert!(seen.0.history.same(&seen_again.0.history));
+}
+
+#[test]
+fn prism_keeps_unchanged_fields() {
+    let mut data = data();
+    let old_lines = lines(&data.1).clone();
+    Screen::EDITOR.with_mut(&mut data.1, |editor| {
+        EditorView::path.with_mut(editor, |path| path.push('~'))
+    });
+    assert!(Arc::ptr_eq(lines(&data.1), &old_lines));
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Document {
+    Open {
+        #[data(same_fn = "PartialEq::eq")]
+        title: Arc<String>,
+        #[data(ignore)]
+        cursor: usize,
+    },
+    Closed,
+}
+
+#[test]
+fn prism_follows_data_attributes() {
+    let title = Arc::new(String::from("notes"));
+    let mut data = Document::Open {
+        title: title.clone(),
+        cursor: 0,
+    };
+    Document::OPEN.with_mut(&mut data, |open| {
+        // Equal to the old title, so it isn't written back.
+        open.title = Arc::new(String::from("notes"));
+        open.cursor = 4;
+    });
+    match &data {
+        Document::Open {
+            title: new_title,
+            cursor,
+        } => {
+            assert!(Arc::ptr_eq(new_title, &title));
+            assert_eq!(*cursor, 4);
+        }
+        Document::Closed => unreachable!(),
+    }
+
+    Document::OPEN
+        .then(OpenView::title)
+        .with_mut(&mut data, |title| *title = Arc::new(String::from("todo")));
+    assert_eq!(
+        Document::OPEN.with(&data, |open| open.title.to_string()),
+        Some(String::from("todo"))
+    );
+}
+
+// Not `Data`, so a variant can only hold it with a `#[data]` attribute saying how to compare it.
+#[derive(Clone, Debug, PartialEq)]
+struct Handle(u32);
+
+#[allow(dead_code)]
+#[derive(Clone, Data, Matcher)]
+enum Terminal {
+    Running {
+        #[data(ignore)]
+        handle: Handle,
+        #[data(same_fn = "PartialEq::eq")]
+        lines: Vec<String>,
+    },
+    Stopped,
+}
+
+#[test]
+fn fields_that_arent_data() {
+    fn inner() -> impl Widget<(u32, Terminal)> {
+        Terminal::shared_matcher()
+            .running(Label::new(|data: &(u32, RunningView), _: &Env| {
+                data.1.lines.join("\n")
+            }))
+            .stopped(SizedBox::empty())
+    }
+    inner();
+
+    let mut data = Terminal::Running {
+        handle: Handle(1),
+        lines: Vec::new(),
+    };
+    Terminal::RUNNING.with_mut(&mut data, |running| {
+        running.handle = Handle(2);
+        running.lines.push(String::from("$ cargo test"));
+    });
+    assert_eq!(
+        Terminal::RUNNING.with(&data, |running| (
+            running.handle.clone(),
+            running.lines.len()
+        )),
+        Some((Handle(2), 1))
+    );
+}